@@ -1,4 +1,4 @@
-#![allow(dead_code)]
+#![allow(dead_code, clippy::enum_variant_names)]
 use std::fmt::{format, Formatter};
 
 use racros::AutoDebug;
@@ -44,7 +44,10 @@ enum Foo3 {
     Foo1,
     Foo2((i32, u32)),
     Foo3(Foo2),
-    Foo4 { a: i32, b: u32 },
+    Foo4 {
+        a: i32,
+        b: u32,
+    },
     #[debug_debug_not_pretty]
     Foo5(Option<String>),
 }
@@ -67,6 +70,236 @@ where
     foo1: &'a T,
 }
 
+#[derive(AutoDebug)]
+struct Meters(f64);
+
+#[derive(AutoDebug)]
+struct Foo6(i32, #[debug_ignore] i32, #[debug_display] MyType);
+
+#[derive(AutoDebug)]
+#[debug_style = "struct"]
+struct Point(#[debug_name = "x"] i32, #[debug_name = "y"] i32);
+
+#[derive(AutoDebug)]
+struct Marker;
+
+#[derive(AutoDebug)]
+struct Foo7 {
+    foo1: Vec<i32>,
+    #[debug_debug]
+    foo2: (i32, i32),
+    #[debug_debug_not_pretty]
+    foo3: (i32, i32),
+}
+
+#[derive(AutoDebug)]
+enum Foo8 {
+    Foo1(#[debug_display] MyType),
+    Foo2(#[debug_value = "hidden"] String),
+    Foo3(#[debug_ignore] String),
+    Foo4 {
+        #[debug_name = "my_a"]
+        a: i32,
+        #[debug_ignore]
+        b: u32,
+        #[debug_value = "c"]
+        c: String,
+        #[debug_display]
+        d: MyType,
+    },
+}
+
+#[derive(AutoDebug)]
+enum Color {
+    Rgb(u8, u8, u8),
+    Rgba(u8, u8, u8, #[debug_ignore] u8),
+    Named(
+        #[debug_display] MyType,
+        #[debug_value = "hidden"] String,
+        u8,
+    ),
+}
+
+#[derive(AutoDebug)]
+#[debug_style = "map"]
+enum Foo9 {
+    Foo1 { a: i32, b: u32 },
+    Foo2(i32, u32),
+}
+
+#[derive(AutoDebug)]
+#[debug_style = "tuple"]
+enum Foo10 {
+    Foo1 { a: i32, b: u32 },
+}
+
+#[derive(AutoDebug)]
+#[debug_style = "map"]
+struct Foo11 {
+    a: i32,
+    b: u32,
+}
+
+#[derive(AutoDebug)]
+enum Foo12 {
+    #[debug_name = "renamed_unit"]
+    Foo1,
+    #[debug_name = "RenamedTuple"]
+    Foo2(i32),
+    #[debug_name = "RenamedStruct"]
+    Foo3 { a: i32 },
+}
+
+#[derive(AutoDebug)]
+struct Account {
+    name: String,
+    #[debug_redact]
+    password: String,
+    #[debug_mask(keep_last = 4)]
+    card: String,
+    #[debug_mask(keep_last = 4)]
+    pin: &'static str,
+}
+
+#[derive(AutoDebug)]
+enum Credential {
+    Token(#[debug_redact] String),
+    Card {
+        #[debug_mask(keep_last = 2)]
+        number: String,
+    },
+}
+
+fn fmt_hex(value: &[u8], f: &mut Formatter<'_>) -> std::fmt::Result {
+    for byte in value {
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
+mod fmt_helper {
+    pub fn secs(value: &std::time::Duration, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}s", value.as_secs())
+    }
+}
+
+#[derive(AutoDebug)]
+struct Packet {
+    #[debug_with = "fmt_hex"]
+    data: Vec<u8>,
+    #[debug_with = "fmt_helper::secs"]
+    timeout: std::time::Duration,
+}
+
+#[derive(AutoDebug)]
+enum Event {
+    Received(#[debug_with = "fmt_hex"] Vec<u8>),
+}
+
+#[derive(AutoDebug)]
+struct Batch {
+    #[debug_value(items.len())]
+    items: Vec<u32>,
+    #[debug_value(self.items.iter().sum::<u32>())]
+    total: (),
+}
+
+#[derive(AutoDebug)]
+struct Buffer(#[debug_value(_0.len())] Vec<u8>);
+
+#[derive(AutoDebug)]
+struct Card {
+    #[debug_value(format!("{}-{}", self.prefix, number))]
+    #[debug_mask(keep_last = 2)]
+    number: String,
+    prefix: u32,
+}
+
+#[derive(AutoDebug)]
+struct Download {
+    #[debug_value(String::from_utf8_lossy(&self.body))]
+    #[debug_truncate(max_len = 5)]
+    body: Vec<u8>,
+}
+
+#[derive(AutoDebug)]
+enum Session {
+    Active(u32),
+    #[debug_ignore]
+    Closed(String),
+    #[debug_ignore]
+    #[debug_name = "Expired"]
+    Timeout { after: u32 },
+}
+
+#[derive(AutoDebug)]
+enum Job {
+    Pending {
+        #[debug_value(tasks.len())]
+        tasks: Vec<String>,
+    },
+    Done(#[debug_value(_0.is_empty())] String),
+}
+
+#[derive(AutoDebug)]
+struct Blob<'a> {
+    #[debug_truncate(max_len = 5)]
+    text: String,
+    #[debug_truncate(max_items = 3)]
+    items: Vec<u32>,
+    slice: &'a [u8],
+    name: &'a str,
+}
+
+#[derive(AutoDebug)]
+#[debug_truncate(max_len = 3, max_items = 2)]
+struct Blob2<'a> {
+    text: String,
+    items: Vec<u32>,
+    slice: &'a [u8],
+    name: &'a str,
+    #[debug_truncate(max_items = 1)]
+    set: std::collections::BTreeSet<u8>,
+    count: u32,
+}
+
+#[derive(AutoDebug)]
+#[debug_truncate(max_items = 2)]
+enum Blob3 {
+    Items(Vec<u32>),
+    Text {
+        #[debug_truncate(max_len = 2)]
+        text: String,
+    },
+}
+
+fn is_zero(v: &u32) -> bool {
+    *v == 0
+}
+
+#[derive(AutoDebug)]
+struct Request {
+    path: String,
+    #[debug_skip_if = "Option::is_none"]
+    query: Option<String>,
+    #[debug_skip_if = "Vec::is_empty"]
+    headers: Vec<String>,
+    #[debug_skip_if = "is_zero"]
+    #[debug_display]
+    retries: u32,
+}
+
+#[derive(AutoDebug)]
+enum Response {
+    Ok(#[debug_skip_if = "Vec::is_empty"] Vec<u8>),
+    Err {
+        code: u32,
+        #[debug_skip_if = "Option::is_none"]
+        #[debug_redact]
+        reason: Option<String>,
+    },
+}
+
 fn main() {
     let foo1 = Foo1 {
         foo1: MyType {},
@@ -98,7 +331,7 @@ fn main() {
     );
 
     let foo31 = Foo3::Foo1;
-    assert_eq!(format(format_args!("{foo31:#?}")), r#""Foo1""#);
+    assert_eq!(format(format_args!("{foo31:#?}")), "Foo1");
 
     let foo32 = Foo3::Foo2((-1, 2));
     assert_eq!(
@@ -128,7 +361,7 @@ fn main() {
     let foo34 = Foo3::Foo4 { a: -100, b: 200 };
     assert_eq!(
         format(format_args!("{foo34:#?}")),
-        r#"{
+        r#"Foo4 {
     a: -100,
     b: 200,
 }"#
@@ -140,13 +373,13 @@ fn main() {
         r#"Foo5(
     Some("hello world"),
 )"#,
-);
+    );
 
     let my_type = MyType {};
     let foo4 = Foo4::Foo3 { a: my_type, b: 4 };
     assert_eq!(
         format(format_args!("{foo4:#?}")),
-        r#"{
+        r#"Foo3 {
     a: debug MyType,
     b: 4,
 }"#
@@ -160,4 +393,304 @@ fn main() {
     foo1: &'a debug MyType,
 }"#
     );
+
+    assert_eq!(format(format_args!("{:?}", Meters(1.5))), "Meters(1.5)");
+
+    let foo6 = Foo6(1, 2, MyType {});
+    assert_eq!(
+        format(format_args!("{foo6:#?}")),
+        r#"Foo6(
+    1,
+    display MyType,
+)"#
+    );
+
+    assert_eq!(
+        format(format_args!("{:?}", Point(1, 2))),
+        "Point { x: 1, y: 2 }"
+    );
+
+    assert_eq!(format(format_args!("{:?}", Marker)), "Marker");
+
+    let foo7 = Foo7 {
+        foo1: vec![1, 2],
+        foo2: (3, 4),
+        foo3: (5, 6),
+    };
+    assert_eq!(
+        format(format_args!("{foo7:?}")),
+        r#"Foo7 { foo1: [1, 2], foo2: (
+    3,
+    4,
+), foo3: (5, 6) }"#
+    );
+    assert_eq!(
+        format(format_args!("{foo7:#?}")),
+        r#"Foo7 {
+    foo1: [
+        1,
+        2,
+    ],
+    foo2: (
+        3,
+        4,
+    ),
+    foo3: (5, 6),
+}"#
+    );
+
+    assert_eq!(
+        format(format_args!("{foo5:?}")),
+        "Foo5 { foo1: &'a debug MyType }"
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Foo3::Foo2((-1, 2)))),
+        "Foo2((-1, 2))"
+    );
+
+    assert_eq!(
+        format(format_args!("{:?}", Foo8::Foo1(MyType {}))),
+        "Foo1(display MyType)"
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Foo8::Foo2(String::from("secret")))),
+        r#"Foo2("hidden")"#
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Foo8::Foo3(String::from("secret")))),
+        "Foo3"
+    );
+    let foo84 = Foo8::Foo4 {
+        a: 1,
+        b: 2,
+        c: String::from("secret"),
+        d: MyType {},
+    };
+    assert_eq!(
+        format(format_args!("{foo84:?}")),
+        r#"Foo4 { my_a: 1, c: "c", d: display MyType }"#
+    );
+
+    assert_eq!(
+        format(format_args!("{:?}", Color::Rgb(1, 2, 3))),
+        "Rgb(1, 2, 3)"
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Color::Rgba(1, 2, 3, 4))),
+        "Rgba(1, 2, 3)"
+    );
+    assert_eq!(
+        format(format_args!("{:#?}", Color::Rgb(1, 2, 3))),
+        r#"Rgb(
+    1,
+    2,
+    3,
+)"#
+    );
+    assert_eq!(
+        format(format_args!(
+            "{:?}",
+            Color::Named(MyType {}, String::from("secret"), 5)
+        )),
+        r#"Named(display MyType, "hidden", 5)"#
+    );
+
+    assert_eq!(
+        format(format_args!("{:?}", Foo9::Foo1 { a: 1, b: 2 })),
+        "{a: 1, b: 2}"
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Foo9::Foo2(1, 2))),
+        "{0: 1, 1: 2}"
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Foo10::Foo1 { a: 1, b: 2 })),
+        "Foo1(1, 2)"
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Foo11 { a: 1, b: 2 })),
+        "{a: 1, b: 2}"
+    );
+
+    assert_eq!(format(format_args!("{:?}", Foo12::Foo1)), "renamed_unit");
+    assert_eq!(
+        format(format_args!("{:?}", Foo12::Foo2(1))),
+        "RenamedTuple(1)"
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Foo12::Foo3 { a: 1 })),
+        "RenamedStruct { a: 1 }"
+    );
+
+    let account = Account {
+        name: String::from("foo"),
+        password: String::from("secret"),
+        card: String::from("1234567812345678"),
+        pin: "1234",
+    };
+    assert_eq!(
+        format(format_args!("{account:?}")),
+        r#"Account { name: "foo", password: ***, card: ***5678, pin: *** }"#
+    );
+    assert_eq!(
+        format(format_args!(
+            "{:?}",
+            Credential::Token(String::from("secret"))
+        )),
+        "Token(***)"
+    );
+    assert_eq!(
+        format(format_args!(
+            "{:?}",
+            Credential::Card {
+                number: String::from("番号1234")
+            }
+        )),
+        "Card { number: ***34 }"
+    );
+
+    let packet = Packet {
+        data: vec![0xde, 0xad, 0xbe, 0xef],
+        timeout: std::time::Duration::from_secs(30),
+    };
+    assert_eq!(
+        format(format_args!("{packet:?}")),
+        "Packet { data: deadbeef, timeout: 30s }"
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Event::Received(vec![0x01, 0xff]))),
+        "Received(01ff)"
+    );
+
+    let batch = Batch {
+        items: vec![1, 2, 3],
+        total: (),
+    };
+    assert_eq!(
+        format(format_args!("{batch:?}")),
+        "Batch { items: 3, total: 6 }"
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Buffer(vec![0; 16]))),
+        "Buffer(16)"
+    );
+    assert_eq!(
+        format(format_args!(
+            "{:?}",
+            Job::Pending {
+                tasks: vec![String::from("a"), String::from("b")]
+            }
+        )),
+        "Pending { tasks: 2 }"
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Job::Done(String::new()))),
+        "Done(true)"
+    );
+    let card = Card {
+        number: String::from("1234"),
+        prefix: 42,
+    };
+    assert_eq!(
+        format(format_args!("{card:?}")),
+        "Card { number: ***34, prefix: 42 }"
+    );
+    assert_eq!(
+        format(format_args!(
+            "{:?} {:?} {:?}",
+            Session::Active(1),
+            Session::Closed(String::from("bye")),
+            Session::Timeout { after: 30 }
+        )),
+        "Active(1) Closed Expired"
+    );
+    let download = Download {
+        body: b"hello world".to_vec(),
+    };
+    assert_eq!(
+        format(format_args!("{download:?}")),
+        r#"Download { body: "hello"... (6 more) }"#
+    );
+
+    let blob = Blob {
+        text: String::from("hello world"),
+        items: vec![1, 2, 3, 4, 5],
+        slice: &[1, 2],
+        name: "foo",
+    };
+    assert_eq!(
+        format(format_args!("{blob:?}")),
+        r#"Blob { text: "hello"... (6 more), items: [1, 2, 3, ... (2 more)], slice: &'a [1, 2], name: &'a "foo" }"#
+    );
+    let blob2 = Blob2 {
+        text: String::from("hello"),
+        items: vec![1, 2],
+        slice: &[1, 2, 3],
+        name: "foobar",
+        set: [1, 2, 3].into_iter().collect(),
+        count: 1,
+    };
+    assert_eq!(
+        format(format_args!("{blob2:?}")),
+        r#"Blob2 { text: "hel"... (2 more), items: [1, 2], slice: &'a [1, 2, ... (1 more)], name: &'a "foo"... (3 more), set: [1, ... (2 more)], count: 1 }"#
+    );
+    assert_eq!(
+        format(format_args!("{:?}", Blob3::Items(vec![1, 2, 3]))),
+        "Items([1, 2, ... (1 more)])"
+    );
+    assert_eq!(
+        format(format_args!(
+            "{:?}",
+            Blob3::Text {
+                text: String::from("abc")
+            }
+        )),
+        r#"Text { text: "ab"... (1 more) }"#
+    );
+
+    let request = Request {
+        path: String::from("/"),
+        query: None,
+        headers: vec![],
+        retries: 0,
+    };
+    assert_eq!(
+        format(format_args!("{request:?}")),
+        r#"Request { path: "/" }"#
+    );
+    let request = Request {
+        path: String::from("/"),
+        query: Some(String::from("a=1")),
+        headers: vec![String::from("Accept")],
+        retries: 3,
+    };
+    assert_eq!(
+        format(format_args!("{request:?}")),
+        r#"Request { path: "/", query: Some("a=1"), headers: ["Accept"], retries: 3 }"#
+    );
+    assert_eq!(format(format_args!("{:?}", Response::Ok(vec![]))), "Ok");
+    assert_eq!(
+        format(format_args!("{:?}", Response::Ok(vec![1]))),
+        "Ok([1])"
+    );
+    assert_eq!(
+        format(format_args!(
+            "{:?}",
+            Response::Err {
+                code: 404,
+                reason: None
+            }
+        )),
+        "Err { code: 404 }"
+    );
+    assert_eq!(
+        format(format_args!(
+            "{:?}",
+            Response::Err {
+                code: 500,
+                reason: Some(String::from("secret"))
+            }
+        )),
+        "Err { code: 500, reason: *** }"
+    );
 }
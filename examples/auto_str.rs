@@ -27,14 +27,165 @@ enum MyEnum3 {
 }
 
 #[derive(AutoStr, Debug)]
+#[auto_str(round_trip)]
 enum MyEnum4 {
     E41(MyEnum),
     E42(MyEnum2),
 }
 
+// "e1" is claimed by both variants, the second arm is unreachable.
+#[allow(unreachable_patterns)]
+mod one_way {
+    use super::MyEnum;
+    use racros::AutoStr;
+
+    #[derive(AutoStr, Debug)]
+    #[auto_str(round_trip)]
+    pub enum MyEnum5 {
+        #[str("e1")]
+        E51(MyEnum),
+        #[str("e1")]
+        E52,
+    }
+}
+
+#[derive(AutoStr, Debug)]
+enum Addr {
+    #[str("localhost")]
+    Localhost,
+    Port(u16),
+    #[str(convert = "parse")]
+    Ip(std::net::Ipv4Addr),
+    #[str("on", "off", convert = "try_from")]
+    Switch(MySwitch),
+}
+
+#[derive(Debug)]
+struct MySwitch(bool);
+
+impl TryFrom<&str> for MySwitch {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(Self(value == "on"))
+    }
+}
+
+impl std::fmt::Display for MySwitch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0 { "on" } else { "off" })
+    }
+}
+
+#[derive(AutoStr, Debug)]
+struct MyPort(u16);
+
+#[derive(AutoStr, Debug)]
+#[autorule = "lowercase"]
+enum Format {
+    #[str("json")]
+    #[str("js", until = "2.0")]
+    Json,
+    #[str(since = "2.1")]
+    Toml,
+    #[str("yml", "yaml", since = "1.1", until = "3")]
+    Yaml,
+    Ini,
+}
+
+#[derive(AutoStr, Debug)]
+#[autorule(rule = "snake_case")]
+enum Protocol {
+    Http2Client,
+    V2Api,
+    Ipv4Addr,
+}
+
+#[derive(AutoStr, Debug)]
+#[autorule(rule = "camelCase", acronyms = ["HTTP", "ID"])]
+enum Field {
+    HttpClient,
+    UserId,
+    Identity,
+}
+
+#[derive(AutoStr, Debug)]
+#[autorule(rule = "PascalCase", acronyms = ["HTTP", "ID"])]
+enum Field2 {
+    HttpClient,
+    UserId,
+    Identity,
+}
+
+#[derive(AutoStr, Debug)]
+#[autorule(rule = "SCREAMING_CASE", digits = "separate")]
+enum Protocol2 {
+    Http2Client,
+    V2Api,
+    Ipv4Addr,
+}
+
+#[derive(AutoStr, Debug)]
+enum Op {
+    #[char('+')]
+    #[str("add")]
+    Add,
+    #[str("-")]
+    Sub,
+    #[char('*', 'x')]
+    Mul,
+}
+
+/// File permission.
+#[derive(AutoStr, Debug)]
+#[auto_str(list, const_fn, json_schema)]
+#[autorule = "lowercase"]
+enum Perm {
+    Read,
+    /// Write, "w" for short.
+    #[str("write", "w")]
+    Write,
+    Exec,
+}
+
+#[derive(AutoStr, Debug)]
+#[str(prefix = "id-", validate = "is_id")]
+struct MyId(String);
+
+fn is_id(value: &str) -> bool {
+    value.chars().all(|c| c.is_ascii_digit())
+}
+
+#[derive(AutoStr, Debug)]
+#[str("marker", "m")]
+struct MyMarker;
+
+#[derive(AutoStr, Debug)]
+#[autorule = "snake_case"]
+struct MyOtherMarker;
+
+#[derive(AutoStr, Debug)]
+struct MyName(MyEnum);
+
+#[derive(AutoStr, Debug)]
+#[str(prefix = "id-", convert = "parse")]
+struct MyGenericId<T: std::str::FromStr + std::fmt::Display>(T)
+where
+    T::Err: std::fmt::Display;
+
 fn main() {
     check_try_from();
     check_to_string();
+    check_round_trip();
+    check_struct();
+    check_list();
+    check_const_fn();
+    check_convert();
+    check_json_schema();
+    check_char();
+    check_version();
+    check_digits();
+    check_acronyms();
 }
 
 #[allow(clippy::cognitive_complexity)]
@@ -90,3 +241,235 @@ fn check_to_string() {
     assert_eq!(MyEnum3::E32TesT.to_string(), "e32test");
     assert_eq!(MyEnum3::E33Test(MyEnum2::E22(MyEnum::E3)).to_string(), "e3");
 }
+
+fn check_round_trip() {
+    assert_eq!(MyEnum4::check_round_trip(), Ok(()));
+    assert_eq!(one_way::MyEnum5::check_round_trip(), Err(vec!["E52"]));
+}
+
+fn check_struct() {
+    assert_eq!(MyId::try_from("id-42").unwrap().0, "42");
+    assert_eq!(MyId(String::from("42")).to_string(), "id-42");
+    assert_eq!(
+        MyId::try_from("42").unwrap_err(),
+        "failed to convert to MyId :missing prefix \"id-\" in \"42\""
+    );
+    assert_eq!(
+        MyId::try_from("id-foo").unwrap_err(),
+        "failed to convert to MyId :invalid value \"foo\""
+    );
+
+    assert!(matches!(MyMarker::try_from("marker"), Ok(MyMarker)));
+    assert!(matches!(MyMarker::try_from("m"), Ok(MyMarker)));
+    assert!(MyMarker::try_from("MyMarker").is_err());
+    assert_eq!(MyMarker.to_string(), "marker");
+
+    assert!(matches!(
+        MyOtherMarker::try_from("my_other_marker"),
+        Ok(MyOtherMarker)
+    ));
+    assert_eq!(MyOtherMarker.to_string(), "my_other_marker");
+
+    assert!(matches!(MyName::try_from("ee"), Ok(MyName(MyEnum::E3))));
+    assert_eq!(MyName(MyEnum::E3).to_string(), "e3");
+    assert_eq!(
+        MyName::try_from("e4").unwrap_err(),
+        "failed to convert to MyName: failed to convert to MyEnum :invalid value \"e4\""
+    );
+
+    assert!(matches!(
+        MyGenericId::<u32>::try_from("id-42"),
+        Ok(MyGenericId(42))
+    ));
+    assert_eq!(MyGenericId(7u8).to_string(), "id-7");
+    assert!(MyGenericId::<u8>::try_from("id-256").is_err());
+}
+
+fn check_list() {
+    let perms = Perm::parse_list("read|write| exec", "|").unwrap();
+    assert!(matches!(perms[..], [Perm::Read, Perm::Write, Perm::Exec]));
+    assert_eq!(Perm::join(&perms, ","), "read,write,exec");
+    assert!(Perm::parse_list("", ",").unwrap().is_empty());
+    assert!(matches!(
+        Perm::parse_list("read,write, ", ",").unwrap()[..],
+        [Perm::Read, Perm::Write]
+    ));
+    assert_eq!(
+        Perm::parse_list("read,,", ",").unwrap_err(),
+        "failed to parse item 1 at position 5: failed to convert to Perm :invalid value \"\""
+    );
+    assert_eq!(
+        Perm::parse_list("read, foo", ",").unwrap_err(),
+        "failed to parse item 1 at position 6: failed to convert to Perm :invalid value \"foo\""
+    );
+
+    assert!(matches!(
+        Perm::parse_list_unique("w,read", ",").unwrap()[..],
+        [Perm::Write, Perm::Read]
+    ));
+    assert_eq!(
+        Perm::parse_list_unique("write,read,w", ",").unwrap_err(),
+        "duplicate value \"write\" in item 2 at position 11, first seen in item 0"
+    );
+}
+
+const DEFAULT_PERM: Option<Perm> = Perm::from_str_const("w");
+
+fn check_const_fn() {
+    assert!(matches!(DEFAULT_PERM, Some(Perm::Write)));
+    assert!(matches!(Perm::from_str_const("read"), Some(Perm::Read)));
+    assert!(matches!(Perm::from_str_const("exec"), Some(Perm::Exec)));
+    assert!(Perm::from_str_const("Exec").is_none());
+    assert!(Perm::from_str_const("").is_none());
+}
+
+fn check_convert() {
+    assert!(matches!(Addr::try_from("localhost"), Ok(Addr::Localhost)));
+    assert!(matches!(Addr::try_from("8080"), Ok(Addr::Port(8080))));
+    assert!(matches!(
+        Addr::try_from("127.0.0.1"),
+        Ok(Addr::Ip(ip)) if ip == std::net::Ipv4Addr::LOCALHOST
+    ));
+    assert!(matches!(
+        Addr::try_from("on"),
+        Ok(Addr::Switch(MySwitch(true)))
+    ));
+    assert!(matches!(
+        Addr::try_from("off"),
+        Ok(Addr::Switch(MySwitch(false)))
+    ));
+    assert!(Addr::try_from("70000").is_err());
+    assert_eq!(Addr::Port(8080).to_string(), "8080");
+    assert_eq!(
+        Addr::Ip(std::net::Ipv4Addr::LOCALHOST).to_string(),
+        "127.0.0.1"
+    );
+    assert_eq!(Addr::Switch(MySwitch(true)).to_string(), "on");
+
+    assert!(matches!(MyPort::try_from("443"), Ok(MyPort(443))));
+    assert_eq!(MyPort(443).to_string(), "443");
+    assert_eq!(
+        MyPort::try_from("-1").unwrap_err(),
+        "failed to convert to MyPort: invalid digit found in string"
+    );
+}
+
+fn check_json_schema() {
+    assert_eq!(
+        Perm::json_schema(),
+        r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "Perm",
+  "description": "File permission.",
+  "type": "string",
+  "enum": ["read", "write", "w", "exec"],
+  "oneOf": [
+    {
+      "title": "Read",
+      "enum": ["read"],
+      "x-canonical": "read"
+    },
+    {
+      "title": "Write",
+      "description": "Write, \"w\" for short.",
+      "enum": ["write", "w"],
+      "x-canonical": "write"
+    },
+    {
+      "title": "Exec",
+      "enum": ["exec"],
+      "x-canonical": "exec"
+    }
+  ]
+}"#
+    );
+}
+
+fn check_char() {
+    assert!(matches!(Op::try_from('+'), Ok(Op::Add)));
+    assert!(matches!(Op::try_from('-'), Ok(Op::Sub)));
+    assert!(matches!(Op::try_from('*'), Ok(Op::Mul)));
+    assert!(matches!(Op::try_from('x'), Ok(Op::Mul)));
+    assert_eq!(
+        Op::try_from('/').unwrap_err(),
+        "failed to convert to Op :invalid value '/'"
+    );
+    assert!(matches!(Op::try_from("add"), Ok(Op::Add)));
+    assert_eq!(Op::Add.to_char(), '+');
+    assert_eq!(Op::Sub.to_char(), '-');
+    assert_eq!(Op::Mul.to_char(), '*');
+}
+
+fn check_version() {
+    assert!(matches!(
+        Format::try_from_version("json", "0.1"),
+        Ok(Format::Json)
+    ));
+    assert!(matches!(
+        Format::try_from_version("js", "1.9.9"),
+        Ok(Format::Json)
+    ));
+    assert_eq!(
+        Format::try_from_version("js", "2.0.0").unwrap_err(),
+        "\"js\" is no longer available since version 2.0, got version 2.0.0"
+    );
+    assert!(matches!(Format::try_from("js"), Ok(Format::Json)));
+
+    assert_eq!(
+        Format::try_from_version("toml", "2").unwrap_err(),
+        "\"toml\" is only available since version 2.1, got version 2"
+    );
+    assert!(matches!(
+        Format::try_from_version("toml", "2.1"),
+        Ok(Format::Toml)
+    ));
+    assert!(matches!(
+        Format::try_from_version("toml", "10.0"),
+        Ok(Format::Toml)
+    ));
+
+    assert!(Format::try_from_version("yaml", "1.0").is_err());
+    assert!(matches!(
+        Format::try_from_version("yml", "1.1"),
+        Ok(Format::Yaml)
+    ));
+    assert!(Format::try_from_version("yml", "3.0").is_err());
+
+    assert!(matches!(
+        Format::try_from_version("ini", "0"),
+        Ok(Format::Ini)
+    ));
+    assert_eq!(
+        Format::try_from_version("xml", "1.0").unwrap_err(),
+        "failed to convert to Format :invalid value \"xml\""
+    );
+    assert_eq!(
+        Format::try_from_version("ini", "1.x").unwrap_err(),
+        "invalid version \"1.x\""
+    );
+}
+
+fn check_digits() {
+    assert_eq!(Protocol::Http2Client.to_string(), "http2_client");
+    assert_eq!(Protocol::V2Api.to_string(), "v2_api");
+    assert_eq!(Protocol::Ipv4Addr.to_string(), "ipv4_addr");
+    assert!(matches!(
+        Protocol::try_from("ipv4_addr"),
+        Ok(Protocol::Ipv4Addr)
+    ));
+
+    assert_eq!(Protocol2::Http2Client.to_string(), "HTTP_2_CLIENT");
+    assert_eq!(Protocol2::V2Api.to_string(), "V_2_API");
+    assert_eq!(Protocol2::Ipv4Addr.to_string(), "IPV_4_ADDR");
+}
+
+fn check_acronyms() {
+    assert_eq!(Field::HttpClient.to_string(), "HTTPClient");
+    assert_eq!(Field::UserId.to_string(), "userID");
+    assert_eq!(Field::Identity.to_string(), "identity");
+    assert!(matches!(Field::try_from("userID"), Ok(Field::UserId)));
+
+    assert_eq!(Field2::HttpClient.to_string(), "HTTPClient");
+    assert_eq!(Field2::UserId.to_string(), "UserID");
+    assert_eq!(Field2::Identity.to_string(), "Identity");
+}
@@ -0,0 +1,85 @@
+use racros::case;
+
+const MAX_CONNECTIONS: u32 = 8;
+
+fn http_client() -> &'static str {
+    "client"
+}
+
+macro_rules! call {
+    ($name:ident) => {
+        case!(snake, $name)()
+    };
+}
+
+macro_rules! constant {
+    ($name:ident) => {
+        (case!(screaming, $name), case!(screaming, $name, str))
+    };
+}
+
+macro_rules! define {
+    ($name:ident, $value:expr) => {
+        case! {
+            fn [<snake $name>]() -> u32 {
+                [<screaming $name>]
+            }
+
+            const [<screaming $name>]: u32 = $value;
+        }
+    };
+}
+
+define!(RetryCount, 3);
+
+case! {
+    struct [<pascal "http" _ "server">];
+    fn [<snake Http Server Name>]() -> &'static str {
+        stringify!([<pascal "http" _ "server">])
+    }
+}
+
+fn main() {
+    let converted = [
+        case!(snake, "HttpClient"),
+        case!(screaming, "HttpClient"),
+        case!(camel, "http_client"),
+        case!(pascal, "http_client"),
+        case!(lower, "HttpClient"),
+        case!(upper, "HttpClient"),
+        case!(SCREAMING_CASE, "HttpClient"),
+        case!(snake, HttpClient, str),
+    ];
+    assert_eq!(
+        converted,
+        [
+            "http_client",
+            "HTTP_CLIENT",
+            "httpClient",
+            "HttpClient",
+            "httpclient",
+            "HTTPCLIENT",
+            "HTTP_CLIENT",
+            "http_client",
+        ]
+    );
+    assert_eq!(case!(snake, "HttpClient", ident)(), "client");
+
+    assert_eq!(call!(HttpClient), "client");
+    assert_eq!(constant!(MaxConnections), (8, "MAX_CONNECTIONS"));
+
+    let case!(snake, MyValue) = 1;
+    assert_eq!(my_value, 1);
+
+    // Only identifiers drop their `r#` prefix, string literals keep all words.
+    let converted = [case!(snake, "r#FooBar"), case!(snake, r#FooBar, str)];
+    assert_eq!(converted, ["r_foo_bar", "foo_bar"]);
+    // Keywords become raw identifiers.
+    let case!(lower, Type) = 2;
+    assert_eq!(r#type, 2);
+
+    assert_eq!(retry_count(), 3);
+    assert_eq!(RETRY_COUNT, 3);
+    let _ = HttpServer;
+    assert_eq!(http_server_name(), "HttpServer");
+}
@@ -0,0 +1,506 @@
+//! # racros-core
+//!
+//! Runtime part of [racros](https://github.com/realth000/racros).
+//!
+//! Case conversion used by `#[autorule = "..."]` in `AutoStr`, the macro depends on this crate so
+//! strings converted at runtime are identical to the ones generated at compile time.
+//!
+//! ```
+//! use racros_core::{to_snake_case, to_snake_case_with, CaseOptions, DigitMode};
+//!
+//! assert_eq!(to_snake_case("HTTPClient"), "http_client");
+//! assert_eq!(to_snake_case("Http2Client"), "http2_client");
+//!
+//! let options = CaseOptions {
+//!     digits: DigitMode::Separate,
+//!     ..Default::default()
+//! };
+//! assert_eq!(to_snake_case_with("Http2Client", &options), "http_2_client");
+//!
+//! let options = CaseOptions {
+//!     acronyms: vec!["HTTP".to_string(), "ID".to_string()],
+//!     ..Default::default()
+//! };
+//! assert_eq!(to_snake_case_with("HttpClientId", &options), "HTTP_client_ID");
+//! ```
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CharKind {
+    /// Uppercase letter.
+    Uppercase,
+    /// Lowercase letter.
+    Lowercase,
+    /// Letter without case, e.g. CJK characters, continues the current word.
+    Caseless,
+    /// Digit.
+    Digit,
+    /// Combining mark, stays with the char before it.
+    Mark,
+    /// Whitespace, punctuation and symbols, separates words and is dropped.
+    Separator,
+}
+
+#[derive(Debug, Clone)]
+struct CharState {
+    /// Kind of current char.
+    kind: CharKind,
+    /// Current char content.
+    content: char,
+}
+
+/// How digits are grouped into words when converting case.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DigitMode {
+    /// Digits belong to the word before them, an uppercase letter after digits starts a new word:
+    ///
+    /// * `Http2Client` => `http2_client`
+    /// * `V2Api` => `v2_api`
+    AttachLeft,
+    /// Digits are a word of their own:
+    ///
+    /// * `Http2Client` => `http_2_client`
+    /// * `V2Api` => `v_2_api`
+    Separate,
+}
+
+/// Options in case conversion.
+#[derive(Debug, Clone)]
+pub struct CaseOptions {
+    /// How digits are grouped into words.
+    pub digits: DigitMode,
+    /// Words kept as is in every case rule, matched case-insensitively against words in the
+    /// input:
+    ///
+    /// * `["HTTP", "ID"]`: `HttpClient` => `HTTPClient` in PascalCase, `UserId` => `userID` in
+    ///   camelCase.
+    pub acronyms: Vec<String>,
+}
+
+impl Default for CaseOptions {
+    fn default() -> Self {
+        Self {
+            digits: DigitMode::AttachLeft,
+            acronyms: vec![],
+        }
+    }
+}
+
+fn prepare_char_state(str: &str) -> Vec<CharState> {
+    let mut state_list: Vec<CharState> = vec![];
+    for ch in str.chars() {
+        let kind = if ch.is_alphabetic() {
+            if ch.is_uppercase() {
+                CharKind::Uppercase
+            } else if ch.is_lowercase() {
+                CharKind::Lowercase
+            } else if ch.to_lowercase().ne(std::iter::once(ch)) {
+                // Title-case letters such as `ǅ` start a word like uppercase ones.
+                CharKind::Uppercase
+            } else {
+                CharKind::Caseless
+            }
+        } else if ch.is_numeric() {
+            CharKind::Digit
+        } else if is_combining_mark(ch) {
+            CharKind::Mark
+        } else {
+            CharKind::Separator
+        };
+        state_list.push(CharState { kind, content: ch });
+    }
+    state_list
+}
+
+/// Whether `ch` is in a block of combining marks.
+///
+/// Marks of most scripts, e.g. Devanagari vowel signs, are alphabetic and already stay in their
+/// word, so only the general purpose blocks are listed here.
+fn is_combining_mark(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{0300}'..='\u{036F}'
+            | '\u{0483}'..='\u{0489}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{200C}'..='\u{200D}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{3099}'..='\u{309A}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+/// Title case of `ch`.
+///
+/// * Digraphs use their title-case form, e.g. `ǆ` => `ǅ`.
+/// * Greek letters with ypogegrammeni use the prosgegrammeni form, e.g. `ᾳ` => `ᾼ`.
+/// * Chars whose uppercase has more than one char, e.g. ligatures, uppercase up to the first
+///   cased char and lowercase the rest, e.g. `ﬁ` => `Fi`, `ß` => `Ss`, `ŉ` => `ʼN`.
+///
+/// Greek letters with both ypogegrammeni and an accent, e.g. `ᾲ`, are not covered and convert to
+/// their uppercase followed by a lowercase iota.
+fn char_convert_titlecase(ch: char) -> String {
+    let title = match ch {
+        '\u{01C4}'..='\u{01C6}' => '\u{01C5}',
+        '\u{01C7}'..='\u{01C9}' => '\u{01C8}',
+        '\u{01CA}'..='\u{01CC}' => '\u{01CB}',
+        '\u{01F1}'..='\u{01F3}' => '\u{01F2}',
+        '\u{1F80}'..='\u{1F87}' | '\u{1F90}'..='\u{1F97}' | '\u{1FA0}'..='\u{1FA7}' => {
+            char::from_u32(ch as u32 + 8).unwrap_or(ch)
+        }
+        '\u{1FB3}' => '\u{1FBC}',
+        '\u{1FC3}' => '\u{1FCC}',
+        '\u{1FF3}' => '\u{1FFC}',
+        _ => {
+            let mut title = String::new();
+            let mut cased = false;
+            for upper in ch.to_uppercase() {
+                if cased {
+                    title.extend(upper.to_lowercase());
+                } else {
+                    cased = upper.is_uppercase();
+                    title.push(upper);
+                }
+            }
+            return title;
+        }
+    };
+    title.to_string()
+}
+
+/// Split `str` into words, the common step of all case conversions.
+///
+/// A new word starts at:
+///
+/// * Separators, e.g. `HTTP_CLIENT` => `HTTP`, `CLIENT`.
+/// * An uppercase letter after a lowercase one, e.g. `httpClient` => `http`, `Client`.
+/// * The last uppercase letter in a run followed by lowercase letters, e.g. `HTTPClient` =>
+///   `HTTP`, `Client`.
+/// * An uppercase letter after a letter without case, e.g. `用户Name` => `用户`, `Name`.
+/// * Digits, according to [`DigitMode`].
+fn split_words(str: &str, options: &CaseOptions) -> Vec<String> {
+    let state_list = prepare_char_state(str);
+
+    let mut words: Vec<String> = vec![];
+    let mut word = String::new();
+    // Kind of the last char pushed into `word`.
+    let mut last_kind: Option<CharKind> = None;
+
+    for (index, state) in state_list.iter().enumerate() {
+        let new_word = match (last_kind, state.kind) {
+            (_, CharKind::Mark) => {
+                word.push(state.content);
+                continue;
+            }
+            (_, CharKind::Separator) => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                last_kind = None;
+                continue;
+            }
+            (None, _) => false,
+            // e.g. httpClient
+            //          |
+            //          current letter.
+            (Some(CharKind::Lowercase), CharKind::Uppercase) => true,
+            // e.g. HTTPClient
+            //          |
+            //          current letter, next letter is lowercase.
+            (Some(CharKind::Uppercase), CharKind::Uppercase) => state_list[index + 1..]
+                .iter()
+                .find(|next| next.kind != CharKind::Mark)
+                .map_or(false, |next| next.kind == CharKind::Lowercase),
+            // e.g. 用户Name
+            //        |
+            //        current letter.
+            (Some(CharKind::Caseless), CharKind::Uppercase) => true,
+            // e.g. V2Api
+            //        |
+            //        current letter.
+            (Some(CharKind::Digit), CharKind::Uppercase) => true,
+            (Some(CharKind::Digit), CharKind::Lowercase | CharKind::Caseless) => {
+                options.digits == DigitMode::Separate
+            }
+            (Some(CharKind::Digit), CharKind::Digit) => false,
+            (Some(_), CharKind::Digit) => options.digits == DigitMode::Separate,
+            _ => false,
+        };
+        if new_word {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(state.content);
+        last_kind = Some(state.kind);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Title case the first letter and lowercase the rest.
+///
+/// The whole word is lowercased at once so context dependent mappings such as the Greek final
+/// sigma are kept.
+fn capitalize(word: &str) -> String {
+    let first = match word.chars().next() {
+        Some(v) => v,
+        None => return String::new(),
+    };
+    let lower = word.to_lowercase();
+    let first_len: usize = first.to_lowercase().map(char::len_utf8).sum();
+    char_convert_titlecase(first) + &lower[first_len..]
+}
+
+/// Find the acronym in `options` that `word` matches.
+fn find_acronym<'a>(word: &str, options: &'a CaseOptions) -> Option<&'a str> {
+    let word = word.to_lowercase();
+    options
+        .acronyms
+        .iter()
+        .find(|acronym| acronym.to_lowercase() == word)
+        .map(String::as_str)
+}
+
+fn lowercase(word: &str) -> String {
+    word.to_lowercase()
+}
+
+fn uppercase(word: &str) -> String {
+    word.to_uppercase()
+}
+
+/// Convert to camelCase:
+///
+/// * `HttpClient` => `httpClient`
+/// * `httpClient` => `httpClient`
+/// * `HTTPClient` => `httpClient`
+/// * `HTTP_CLIENT` => `httpClient`
+pub fn to_camel_case(str: &str) -> String {
+    to_camel_case_with(str, &CaseOptions::default())
+}
+
+/// Convert to camelCase with options.
+pub fn to_camel_case_with(str: &str, options: &CaseOptions) -> String {
+    split_words(str, options)
+        .iter()
+        .enumerate()
+        .map(|(index, word)| {
+            if let Some(acronym) = find_acronym(word, options) {
+                acronym.to_string()
+            } else if index == 0 {
+                lowercase(word)
+            } else {
+                capitalize(word)
+            }
+        })
+        .collect()
+}
+
+/// Convert to PascalCase:
+///
+/// * `HttpClient` => `HttpClient`
+/// * `httpClient` => `HttpClient`
+/// * `HTTPClient` => `HttpClient`
+pub fn to_pascal_case(str: &str) -> String {
+    to_pascal_case_with(str, &CaseOptions::default())
+}
+
+/// Convert to PascalCase with options.
+pub fn to_pascal_case_with(str: &str, options: &CaseOptions) -> String {
+    split_words(str, options)
+        .iter()
+        .map(|word| find_acronym(word, options).map_or_else(|| capitalize(word), String::from))
+        .collect()
+}
+
+/// Convert to snake case:
+///
+/// * `HttpClient` => `http_client`
+/// * `httpClient` => `http_client`
+/// * `HTTPClient` => `http_client`
+pub fn to_snake_case(str: &str) -> String {
+    to_snake_case_with(str, &CaseOptions::default())
+}
+
+/// Convert to snake case with options.
+pub fn to_snake_case_with(str: &str, options: &CaseOptions) -> String {
+    split_words(str, options)
+        .iter()
+        .map(|word| find_acronym(word, options).map_or_else(|| lowercase(word), String::from))
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Convert to screaming case:
+///
+/// * `HttpClient` => `HTTP_CLIENT`
+/// * `httpClient` => `HTTP_CLIENT`
+/// * `HTTPClient` => `HTTP_CLIENT`
+pub fn to_screaming_case(str: &str) -> String {
+    to_screaming_case_with(str, &CaseOptions::default())
+}
+
+/// Convert to screaming case with options.
+pub fn to_screaming_case_with(str: &str, options: &CaseOptions) -> String {
+    split_words(str, options)
+        .iter()
+        .map(|word| find_acronym(word, options).map_or_else(|| uppercase(word), String::from))
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRS1: [&str; 4] = ["httpClient", "HttpClient", "HTTPClient", "HTTP_CLIENT"];
+
+    const STRS_DIGITS: [&str; 4] = ["http2Client", "Http2Client", "HTTP2Client", "HTTP2_CLIENT"];
+
+    const SEPARATE: CaseOptions = CaseOptions {
+        digits: DigitMode::Separate,
+        acronyms: vec![],
+    };
+
+    #[test]
+    fn test_to_camel_case() {
+        for s in STRS1 {
+            assert_eq!(to_camel_case(s), "httpClient");
+        }
+    }
+
+    #[test]
+    fn test_to_pascal_case() {
+        for s in STRS1 {
+            assert_eq!(to_pascal_case(s), "HttpClient");
+        }
+    }
+
+    #[test]
+    fn test_snake_case() {
+        for s in STRS1 {
+            assert_eq!(to_snake_case(s), "http_client");
+        }
+    }
+
+    #[test]
+    fn test_screaming_case() {
+        for s in STRS1 {
+            assert_eq!(to_screaming_case(s), "HTTP_CLIENT");
+        }
+    }
+
+    #[test]
+    fn test_digits_attach_left() {
+        for s in STRS_DIGITS {
+            assert_eq!(to_camel_case(s), "http2Client");
+            assert_eq!(to_pascal_case(s), "Http2Client");
+            assert_eq!(to_snake_case(s), "http2_client");
+            assert_eq!(to_screaming_case(s), "HTTP2_CLIENT");
+        }
+        assert_eq!(to_snake_case("V2Api"), "v2_api");
+        assert_eq!(to_snake_case("Ipv4Addr"), "ipv4_addr");
+        assert_eq!(to_snake_case("utf8mb4"), "utf8mb4");
+        assert_eq!(to_snake_case("Version10"), "version10");
+        assert_eq!(to_snake_case("x86_64"), "x86_64");
+    }
+
+    #[test]
+    fn test_digits_separate() {
+        for s in STRS_DIGITS {
+            assert_eq!(to_camel_case_with(s, &SEPARATE), "http2Client");
+            assert_eq!(to_pascal_case_with(s, &SEPARATE), "Http2Client");
+            assert_eq!(to_snake_case_with(s, &SEPARATE), "http_2_client");
+            assert_eq!(to_screaming_case_with(s, &SEPARATE), "HTTP_2_CLIENT");
+        }
+        assert_eq!(to_snake_case_with("V2Api", &SEPARATE), "v_2_api");
+        assert_eq!(to_snake_case_with("Ipv4Addr", &SEPARATE), "ipv_4_addr");
+        assert_eq!(to_snake_case_with("utf8mb4", &SEPARATE), "utf_8_mb_4");
+        assert_eq!(to_snake_case_with("Version10", &SEPARATE), "version_10");
+    }
+
+    #[test]
+    fn test_acronyms() {
+        let options = CaseOptions {
+            acronyms: vec!["HTTP".to_string(), "ID".to_string()],
+            ..Default::default()
+        };
+        for s in STRS1 {
+            assert_eq!(to_camel_case_with(s, &options), "HTTPClient");
+            assert_eq!(to_pascal_case_with(s, &options), "HTTPClient");
+            assert_eq!(to_snake_case_with(s, &options), "HTTP_client");
+            assert_eq!(to_screaming_case_with(s, &options), "HTTP_CLIENT");
+        }
+        assert_eq!(to_camel_case_with("UserId", &options), "userID");
+        assert_eq!(to_pascal_case_with("user_id", &options), "UserID");
+        assert_eq!(to_snake_case_with("userID", &options), "user_ID");
+        // Only whole words are matched.
+        assert_eq!(to_pascal_case_with("IDENTITY", &options), "Identity");
+        assert_eq!(to_pascal_case_with("HttpsProxy", &options), "HttpsProxy");
+    }
+
+    #[test]
+    fn test_unicode_german() {
+        assert_eq!(to_screaming_case("straßeName"), "STRASSE_NAME");
+        assert_eq!(to_pascal_case("straße_name"), "StraßeName");
+        assert_eq!(to_snake_case("StraßeName"), "straße_name");
+    }
+
+    #[test]
+    fn test_unicode_turkish_dotted_i() {
+        assert_eq!(to_snake_case("İstanbulCity"), "i\u{307}stanbul_city");
+        assert_eq!(to_camel_case("İSTANBUL_CITY"), "i\u{307}stanbulCity");
+        assert_eq!(to_screaming_case("ıstanbul"), "ISTANBUL");
+        // The combining dot stays in its word.
+        assert_eq!(
+            to_pascal_case("i\u{307}stanbul_city"),
+            "I\u{307}stanbulCity"
+        );
+    }
+
+    #[test]
+    fn test_unicode_greek_final_sigma() {
+        assert_eq!(to_snake_case("ΟΔΟΣ_ΚΑΛΟΣ"), "οδος_καλος");
+        assert_eq!(to_pascal_case("ΟΔΟΣ_ΚΑΛΟΣ"), "ΟδοςΚαλος");
+        assert_eq!(to_camel_case("ΟΔΟΣ_ΚΑΛΟΣ"), "οδοςΚαλος");
+        assert_eq!(to_screaming_case("οδοςΚαλος"), "ΟΔΟΣ_ΚΑΛΟΣ");
+    }
+
+    #[test]
+    fn test_unicode_cjk() {
+        assert_eq!(to_snake_case("用户Name"), "用户_name");
+        assert_eq!(to_snake_case("HTTP用户"), "http用户");
+        assert_eq!(to_pascal_case("用户_名前"), "用户名前");
+        assert_eq!(to_camel_case("HttpClient用户"), "httpClient用户");
+        assert_eq!(to_snake_case("ユーザーID"), "ユーザー_id");
+        assert_eq!(to_snake_case_with("用户2名前", &SEPARATE), "用户_2_名前");
+    }
+
+    #[test]
+    fn test_unicode_title_case() {
+        assert_eq!(to_snake_case("ǅemalBey"), "ǆemal_bey");
+        assert_eq!(to_pascal_case("ǆemal_bey"), "ǅemalBey");
+        assert_eq!(to_screaming_case("ǅemalBey"), "ǄEMAL_BEY");
+    }
+
+    #[test]
+    fn test_unicode_ligatures() {
+        assert_eq!(to_pascal_case("ﬁleName"), "FileName");
+        assert_eq!(to_camel_case("name_ﬁle"), "nameFile");
+        assert_eq!(to_pascal_case("ßtraße"), "Sstraße");
+        assert_eq!(to_pascal_case("ŉ_ᾳ"), "ʼNᾼ");
+        // Not covered: ypogegrammeni with an accent.
+        assert_eq!(to_pascal_case("ᾲ"), "Ὰι");
+    }
+
+    #[test]
+    fn test_unicode_punctuation() {
+        assert_eq!(to_snake_case("foo—bar"), "foo_bar");
+        assert_eq!(to_pascal_case("foo·bar…baz"), "FooBarBaz");
+        assert_eq!(to_snake_case("«FooBar»"), "foo_bar");
+        assert_eq!(to_camel_case("price€Value"), "priceValue");
+        // Combining marks stay in their word.
+        assert_eq!(to_snake_case("Cafe\u{301}Bar"), "cafe\u{301}_bar");
+    }
+}
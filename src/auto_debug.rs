@@ -5,16 +5,20 @@ use quote::quote;
 use syn::spanned::Spanned;
 use syn::{
     parse_macro_input, Attribute, Data, DataEnum, DataStruct, DeriveInput, Expr, ExprLit, Field,
-    Fields, Lit, Meta, MetaNameValue,
+    Fields, Index, Lit, LitInt, Meta, MetaNameValue, Path, Type, TypeGroup, TypeParen, TypePath,
+    TypeReference,
 };
 
 use crate::util::compiling_error;
 
+#[derive(Clone, Copy)]
 enum DebugStyle {
     Struct,
     Tuple,
+    Map,
 }
 
+#[derive(Clone, Copy)]
 enum DebugFormat {
     Debug,
     Display,
@@ -42,7 +46,7 @@ pub fn auto_debug_internal(input: TokenStream) -> TokenStream {
 
 #[allow(clippy::too_many_lines)]
 fn auto_debug_struct(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream {
-    let mut debug_style = DebugStyle::Struct;
+    let mut debug_style: Option<DebugStyle> = None;
     let mut debug_format = DebugFormat::Debug;
 
     let target_ident = &ast.ident;
@@ -51,8 +55,21 @@ fn auto_debug_struct(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
 
     let mut field_vec: Vec<proc_macro2::TokenStream> = vec![];
 
+    // Whether `AutoDebugFn` is used by any field.
+    let mut use_fn_wrapper = false;
+
+    // `#[debug_truncate(...)]` on struct.
+    let mut truncate_default = TruncateDefault::default();
+
     // Check attributes on struct.
     for attr in &ast.attrs {
+        if attr.path().is_ident("debug_truncate") {
+            truncate_default = match parse_truncate(attr) {
+                Ok(v) => v,
+                Err(e) => return e.to_compile_error().into(),
+            };
+            continue;
+        }
         if let Attribute {
             meta:
                 Meta::NameValue(MetaNameValue {
@@ -74,7 +91,7 @@ fn auto_debug_struct(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
             match path.segments.last().unwrap().ident.to_string().as_str() {
                 "debug_style" => {
                     debug_style = match check_debug_style(token.token().to_string().as_str()) {
-                        Some(v) => v,
+                        Some(v) => Some(v),
                         None => {
                             return compiling_error!(token.span(), "invalid debug_style");
                         }
@@ -95,6 +112,24 @@ fn auto_debug_struct(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
 
     let target_ident_str = target_ident.to_string();
 
+    // Unit struct, only print its name.
+    if let Fields::Unit = &data_struct.fields {
+        let expand = quote! {
+            impl #impl_generics std::fmt::Debug for #target_ident #ty_generics #where_clause {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(#target_ident_str)
+                }
+            }
+        };
+        return expand.into();
+    }
+
+    // Tuple structs print in tuple style unless `debug_style` is set.
+    let debug_style = debug_style.unwrap_or(match &data_struct.fields {
+        Fields::Unnamed(_) => DebugStyle::Tuple,
+        _ => DebugStyle::Struct,
+    });
+
     let field_vec_header = match debug_style {
         DebugStyle::Struct => quote! {
             let mut ff = f.debug_struct(#target_ident_str);
@@ -102,113 +137,90 @@ fn auto_debug_struct(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
         DebugStyle::Tuple => quote! {
             let mut ff = f.debug_tuple(#target_ident_str);
         },
+        DebugStyle::Map => quote! {
+            let mut ff = f.debug_map();
+        },
     };
 
-    for field in &data_struct.fields {
-        let mut field_override_name: Option<String> = None;
-        let mut field_override_value: Option<String> = None;
-        let mut field_ignore = false;
-        let mut field_format: Option<DebugFormat> = None;
-        if let Field {
-            ident: Some(field_ident),
-            attrs,
-            ty,
-            ..
-        } = &field
-        {
-            // Check field attr.
-            for attr in attrs {
-                if let Attribute {
-                    meta:
-                        Meta::NameValue(MetaNameValue {
-                            path,
-                            value:
-                                Expr::Lit(ExprLit {
-                                    lit: Lit::Str(token, ..),
-                                    ..
-                                }),
-                            ..
-                        }),
-                    ..
-                } = &attr
-                {
-                    if path.segments.is_empty() {
-                        continue;
-                    }
-
-                    match path.segments.last().unwrap().ident.to_string().as_str() {
-                        "debug_name" => {
-                            field_override_name =
-                                Some(token.token().to_string().trim_matches('"').to_string());
-                        }
-                        "debug_value" => {
-                            field_override_value =
-                                Some(token.token().to_string().trim_matches('"').to_string());
-                        }
-                        _ => continue,
-                    }
-                } else if let Attribute {
-                    meta: Meta::Path(path),
-                    ..
-                } = &attr
-                {
-                    match path.segments.last().unwrap().ident.to_string().as_str() {
-                        "debug_ignore" => field_ignore = true,
-                        "debug_display" => field_format = Some(DebugFormat::Display),
-                        "debug_debug" => field_format = Some(DebugFormat::Debug),
-                        "debug_debug_not_pretty" => field_format = Some(DebugFormat::DebugNotPretty),
-                        _ => continue,
-                    }
-                }
-            }
+    for (field_index, field) in data_struct.fields.iter().enumerate() {
+        let field_attrs = match parse_field_attrs(&field.attrs) {
+            Ok(v) => v,
+            Err(e) => return e.to_compile_error().into(),
+        };
 
-            if field_ignore {
-                continue;
-            }
+        if field_attrs.ignore {
+            continue;
+        }
 
-            let field_debug_name =
-                field_override_name.map_or_else(|| field_ident.to_string(), |v| v);
-
-            let mut raw_field_placeholder = match field_format {
-                Some(DebugFormat::Debug) => PLACEHOLDER_DEBUG,
-                Some(DebugFormat::Display) => PLACEHOLDER_DISPLAY,
-                Some(DebugFormat::DebugNotPretty) => PLACEHOLDER_DEBUG_NOT_PRETTY,
-                None => match debug_format {
-                    DebugFormat::Debug => PLACEHOLDER_DEBUG,
-                    DebugFormat::Display => PLACEHOLDER_DISPLAY,
-                    DebugFormat::DebugNotPretty=> PLACEHOLDER_DEBUG_NOT_PRETTY,
-                },
-            }
-            .to_string();
-
-            // If this field is a reference type, prepend "&'{lifetime} " to placeholder.
-            if let syn::Type::Reference(syn::TypeReference {
-                lifetime: Some(syn::Lifetime { ident, .. }),
-                ..
-            }) = ty
-            {
-                raw_field_placeholder = format!("&'{ident} {raw_field_placeholder}");
+        // Named fields are accessed by name, fields in tuple structs are accessed by index.
+        let (field_access, field_name) = match &field.ident {
+            Some(field_ident) => (quote! {self.#field_ident}, field_ident.to_string()),
+            None => {
+                let index = Index::from(field_index);
+                (quote! {self.#index}, field_index.to_string())
             }
+        };
 
-            let field_value =
-                field_override_value.map_or_else(|| quote! {self.#field_ident}, |v| quote! {#v});
+        let field_debug_name = field_attrs.name.clone().unwrap_or(field_name);
 
-            let field_placeholder = raw_field_placeholder.as_str();
+        let field_format = resolve_format(field_attrs.format, debug_format);
 
-            match debug_style {
-                DebugStyle::Struct => field_vec.push(quote! {
-                    ff.field(#field_debug_name, &format_args!(#field_placeholder, #field_value));
-                }),
-                DebugStyle::Tuple => field_vec.push(quote! {
-                    ff.field(&format_args!(#field_placeholder, #field_value));
+        // If this field is a reference type, prepend "&'{lifetime} " to value.
+        let field_prefix = match (&field.ty, &field_attrs.value) {
+            (_, Some(FieldValue::Expr(_))) => None,
+            (
+                syn::Type::Reference(syn::TypeReference {
+                    lifetime: Some(syn::Lifetime { ident, .. }),
+                    ..
                 }),
-            }
-        }
+                _,
+            ) => Some(format!("&'{ident} ")),
+            _ => None,
+        };
+
+        let field_value = field_value_expr(
+            &field_attrs.value,
+            &quote! {&#field_access},
+            &field_binding(field, field_index),
+        );
+
+        let field_value = field_value_tokens(
+            &field_value,
+            &field_attrs,
+            field_format,
+            resolve_truncate(&field_attrs, &field.ty, truncate_default),
+            field_prefix,
+            &mut use_fn_wrapper,
+        );
+
+        let field_stmt = match debug_style {
+            DebugStyle::Struct => quote! {
+                ff.field(#field_debug_name, #field_value);
+            },
+            DebugStyle::Tuple => quote! {
+                ff.field(#field_value);
+            },
+            DebugStyle::Map => quote! {
+                ff.key(&format_args!("{}", #field_debug_name)).value(#field_value);
+            },
+        };
+        field_vec.push(skip_if_wrap(
+            field_stmt,
+            &field_attrs.skip_if,
+            &quote! {&#field_access},
+        ));
     }
 
+    let fn_wrapper = if use_fn_wrapper {
+        fn_wrapper_def()
+    } else {
+        quote! {}
+    };
+
     let expand = quote! {
         impl #impl_generics std::fmt::Debug for #target_ident #ty_generics #where_clause {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                #fn_wrapper
                 #field_vec_header
                 #(#field_vec)*
                 ff.finish()
@@ -219,8 +231,495 @@ fn auto_debug_struct(ast: &DeriveInput, data_struct: &DataStruct) -> TokenStream
     expand.into()
 }
 
+/// Format of a field, `None` means `Debug` following the caller, `{:?}` or `{:#?}`.
+///
+/// `#[debug_debug]` and friends on the field take precedence over `#[debug_format = "..."]` on the
+/// container, `debug` on the container is the same as not set.
+fn resolve_format(field: Option<DebugFormat>, container: DebugFormat) -> Option<DebugFormat> {
+    field.or(match container {
+        DebugFormat::Debug => None,
+        v => Some(v),
+    })
+}
+
+fn placeholder(format: DebugFormat) -> &'static str {
+    match format {
+        DebugFormat::Debug => PLACEHOLDER_DEBUG,
+        DebugFormat::Display => PLACEHOLDER_DISPLAY,
+        DebugFormat::DebugNotPretty => PLACEHOLDER_DEBUG_NOT_PRETTY,
+    }
+}
+
+/// Definition of `AutoDebugFn`, a `Debug` implementation calling the closure it wraps.
+///
+/// Used when a field is printed by custom code that still follows flags of the caller's
+/// formatter.
+fn fn_wrapper_def() -> proc_macro2::TokenStream {
+    quote! {
+        struct AutoDebugFn<F>(F)
+        where
+            F: Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+
+        impl<F> std::fmt::Debug for AutoDebugFn<F>
+        where
+            F: Fn(&mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+        {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                (self.0)(f)
+            }
+        }
+    }
+}
+
+/// Expression to pass to `DebugStruct::field` and friends.
+///
+/// `value` is an expression of reference to the field value, `prefix` is printed before the value.
+fn format_field_value(
+    value: &proc_macro2::TokenStream,
+    format: Option<DebugFormat>,
+    prefix: Option<String>,
+    use_fn_wrapper: &mut bool,
+) -> proc_macro2::TokenStream {
+    match (format, prefix) {
+        (None, None) => quote! { #value },
+        (Some(format), None) => {
+            let placeholder = placeholder(format);
+            quote! { &format_args!(#placeholder, #value) }
+        }
+        (format, Some(prefix)) => {
+            *use_fn_wrapper = true;
+            let write_value = match format {
+                None => quote! { std::fmt::Debug::fmt(#value, f) },
+                Some(format) => {
+                    let placeholder = placeholder(format);
+                    quote! { f.write_fmt(format_args!(#placeholder, #value)) }
+                }
+            };
+            quote! {
+                &AutoDebugFn(|f: &mut std::fmt::Formatter<'_>| {
+                    f.write_str(#prefix)?;
+                    #write_value
+                })
+            }
+        }
+    }
+}
+
+/// Only run `stmt` printing a field if the `debug_skip_if` predicate returns false.
+///
+/// `access` is an expression of reference to the field.
+fn skip_if_wrap(
+    stmt: proc_macro2::TokenStream,
+    skip_if: &Option<Path>,
+    access: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match skip_if {
+        Some(skip_if) => quote! {
+            if !#skip_if(#access) {
+                #stmt
+            }
+        },
+        None => stmt,
+    }
+}
+
+/// Expression to pass to `DebugStruct::field` and friends according to attributes on the field.
+///
+/// Redaction takes precedence over `debug_with`, which takes precedence over formats.
+fn field_value_tokens(
+    value: &proc_macro2::TokenStream,
+    field_attrs: &FieldAttrs,
+    format: Option<DebugFormat>,
+    truncate: Option<Truncate>,
+    prefix: Option<String>,
+    use_fn_wrapper: &mut bool,
+) -> proc_macro2::TokenStream {
+    if let Some(redact) = field_attrs.redact {
+        return redact_field_value(value, redact, use_fn_wrapper);
+    }
+    if let Some(with) = &field_attrs.with {
+        *use_fn_wrapper = true;
+        return quote! {
+            &AutoDebugFn(|f: &mut std::fmt::Formatter<'_>| #with(#value, f))
+        };
+    }
+    if let Some(truncate) = truncate {
+        return truncate_field_value(value, truncate, prefix, use_fn_wrapper);
+    }
+    format_field_value(value, format, prefix, use_fn_wrapper)
+}
+
+/// Expression to pass to `DebugStruct::field` and friends for a truncated field.
+///
+/// `value` is an expression of reference to the field value, which implements `AsRef<str>` for
+/// `max_len` and has an `iter()` method for `max_items`.
+fn truncate_field_value(
+    value: &proc_macro2::TokenStream,
+    truncate: Truncate,
+    prefix: Option<String>,
+    use_fn_wrapper: &mut bool,
+) -> proc_macro2::TokenStream {
+    *use_fn_wrapper = true;
+    let write_prefix = prefix.map(|prefix| quote! { f.write_str(#prefix)?; });
+    let write_value = match truncate {
+        Truncate::MaxLen(max_len) => quote! {
+            // Bind first to extend the lifetime of temporaries from `debug_value(expr)`.
+            let value = #value;
+            let value: &str = std::convert::AsRef::<str>::as_ref(value);
+            match value.char_indices().nth(#max_len) {
+                Some((index, _)) => {
+                    std::fmt::Debug::fmt(&value[..index], f)?;
+                    f.write_fmt(format_args!("... ({} more)", value[index..].chars().count()))
+                }
+                None => std::fmt::Debug::fmt(value, f),
+            }
+        },
+        Truncate::MaxItems(max_items) => quote! {
+            let mut list = f.debug_list();
+            let mut count: usize = 0;
+            for item in (#value).iter() {
+                if count < #max_items {
+                    list.entry(&item);
+                }
+                count += 1;
+            }
+            if count > #max_items {
+                list.entry(&format_args!("... ({} more)", count - #max_items));
+            }
+            list.finish()
+        },
+    };
+    quote! {
+        &AutoDebugFn(|f: &mut std::fmt::Formatter<'_>| {
+            #write_prefix
+            #write_value
+        })
+    }
+}
+
+/// Expression to pass to `DebugStruct::field` and friends for a redacted field.
+///
+/// `value` is an expression of reference to the field value, which implements `AsRef<str>` when
+/// masked.
+fn redact_field_value(
+    value: &proc_macro2::TokenStream,
+    redact: Redact,
+    use_fn_wrapper: &mut bool,
+) -> proc_macro2::TokenStream {
+    match redact {
+        Redact::All => quote! { &format_args!("***") },
+        Redact::KeepLast(keep_last) => {
+            *use_fn_wrapper = true;
+            quote! {
+                &AutoDebugFn(|f: &mut std::fmt::Formatter<'_>| {
+                    // Bind first to extend the lifetime of temporaries from `debug_value(expr)`.
+                    let value = #value;
+                    let value: &str = std::convert::AsRef::<str>::as_ref(value);
+                    f.write_str("***")?;
+                    // Values not longer than the kept part are fully hidden.
+                    match value.char_indices().rev().nth(#keep_last) {
+                        Some((index, ch)) => f.write_str(&value[index + ch.len_utf8()..]),
+                        None => Ok(()),
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// Attributes on a field.
+#[derive(Default)]
+struct FieldAttrs {
+    /// `#[debug_name = "..."]`.
+    name: Option<String>,
+    /// `#[debug_value = "..."]` or `#[debug_value(...)]`.
+    value: Option<FieldValue>,
+    /// `#[debug_ignore]`.
+    ignore: bool,
+    /// `#[debug_debug]`, `#[debug_display]` or `#[debug_debug_not_pretty]`.
+    format: Option<DebugFormat>,
+    /// `#[debug_redact]` or `#[debug_mask(...)]`.
+    redact: Option<Redact>,
+    /// `#[debug_with = "..."]`, path to a `fn(&T, &mut Formatter) -> std::fmt::Result`.
+    with: Option<Path>,
+    /// `#[debug_truncate(...)]`.
+    truncate: Option<Truncate>,
+    /// `#[debug_skip_if = "..."]`, path to a `fn(&T) -> bool`.
+    skip_if: Option<Path>,
+}
+
+/// Value printed instead of the field.
+enum FieldValue {
+    /// `#[debug_value = "..."]`, a string literal.
+    Str(String),
+    /// `#[debug_value(...)]`, an expression that may refer to `self` and the field.
+    Expr(proc_macro2::TokenStream),
+}
+
+/// Name the field is bound to in `#[debug_value(...)]`, field name for named fields and `_0`,
+/// `_1`... for unnamed fields.
+fn field_binding(field: &Field, field_index: usize) -> Ident {
+    field
+        .ident
+        .clone()
+        .unwrap_or_else(|| Ident::new(format!("_{field_index}").as_str(), field.span()))
+}
+
+/// Expression of reference to the value to print for a field.
+///
+/// `access` is an expression of reference to the field, `binding` is the name of the field in
+/// `#[debug_value(...)]`.
+fn field_value_expr(
+    value: &Option<FieldValue>,
+    access: &proc_macro2::TokenStream,
+    binding: &Ident,
+) -> proc_macro2::TokenStream {
+    match value {
+        None => quote! { #access },
+        Some(FieldValue::Str(v)) => quote! { &#v },
+        Some(FieldValue::Expr(expr)) => quote! {
+            &{
+                #[allow(unused_variables)]
+                let #binding = #access;
+                #expr
+            }
+        },
+    }
+}
+
+/// How a long field is truncated.
+#[derive(Clone, Copy)]
+enum Truncate {
+    /// `#[debug_truncate(max_len = N)]`, print the first N chars of a string.
+    MaxLen(usize),
+    /// `#[debug_truncate(max_items = N)]`, print the first N items of a collection as a list.
+    MaxItems(usize),
+}
+
+/// `#[debug_truncate(...)]` on the container, applied to fields by their types.
+#[derive(Clone, Copy, Default)]
+struct TruncateDefault {
+    /// Applied to `String` and `str` fields.
+    max_len: Option<usize>,
+    /// Applied to `Vec`, `VecDeque`, `LinkedList`, `HashSet`, `BTreeSet`, slice and array fields.
+    max_items: Option<usize>,
+}
+
+/// Parse `#[debug_truncate(max_len = ..., max_items = ...)]`.
+fn parse_truncate(attr: &Attribute) -> syn::Result<TruncateDefault> {
+    let mut truncate = TruncateDefault::default();
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("max_len") {
+            let value: LitInt = meta.value()?.parse()?;
+            truncate.max_len = Some(value.base10_parse()?);
+            Ok(())
+        } else if meta.path.is_ident("max_items") {
+            let value: LitInt = meta.value()?.parse()?;
+            truncate.max_items = Some(value.base10_parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unknown debug_truncate key, available keys: max_len, max_items"))
+        }
+    })?;
+    if truncate.max_len.is_none() && truncate.max_items.is_none() {
+        return Err(syn::Error::new(
+            attr.meta.span(),
+            "expected #[debug_truncate(max_len = ...)] or #[debug_truncate(max_items = ...)]",
+        ));
+    }
+    Ok(truncate)
+}
+
+/// Truncation of a field, `#[debug_truncate(...)]` on the field or the container default that
+/// fits the field type.
+///
+/// The container default is not applied to fields with `debug_value`.
+fn resolve_truncate(
+    field_attrs: &FieldAttrs,
+    ty: &Type,
+    truncate_default: TruncateDefault,
+) -> Option<Truncate> {
+    if field_attrs.truncate.is_some() || field_attrs.value.is_some() {
+        return field_attrs.truncate;
+    }
+    match ty {
+        Type::Reference(TypeReference { elem, .. })
+        | Type::Group(TypeGroup { elem, .. })
+        | Type::Paren(TypeParen { elem, .. }) => {
+            resolve_truncate(field_attrs, elem, truncate_default)
+        }
+        Type::Slice(_) | Type::Array(_) => truncate_default.max_items.map(Truncate::MaxItems),
+        Type::Path(TypePath { path, .. }) => {
+            match path.segments.last()?.ident.to_string().as_str() {
+                "String" | "str" => truncate_default.max_len.map(Truncate::MaxLen),
+                "Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" => {
+                    truncate_default.max_items.map(Truncate::MaxItems)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// How a secret field is hidden.
+#[derive(Clone, Copy)]
+enum Redact {
+    /// `#[debug_redact]`, print `***`.
+    All,
+    /// `#[debug_mask(keep_last = N)]`, print `***` followed by the last N chars of a string.
+    KeepLast(usize),
+}
+
+/// Attributes allowed on enum variants.
+const VARIANT_ATTRS: &[&str] = &[
+    "debug_name",
+    "debug_ignore",
+    "debug_debug",
+    "debug_display",
+    "debug_debug_not_pretty",
+];
+
+/// Reject attributes that do not work on enum variants.
+///
+/// Field attributes on a variant would be silently ignored, e.g. redaction would print secrets in
+/// clear.
+fn check_variant_attrs(attrs: &[Attribute]) -> syn::Result<()> {
+    for attr in attrs {
+        let name = match attr.path().get_ident() {
+            Some(v) => v.to_string(),
+            None => continue,
+        };
+        if !name.starts_with("debug_") || VARIANT_ATTRS.contains(&name.as_str()) {
+            continue;
+        }
+        if name == "debug_redact" || name == "debug_mask" {
+            return Err(syn::Error::new(
+                attr.meta.span(),
+                "debug_redact/debug_mask must be placed on fields",
+            ));
+        }
+        return Err(syn::Error::new(
+            attr.meta.span(),
+            format!(
+                "{} is not supported on enum variants, available attributes: {}",
+                name,
+                VARIANT_ATTRS.join(", ")
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_field_attrs(attrs: &[Attribute]) -> syn::Result<FieldAttrs> {
+    let mut field_attrs = FieldAttrs::default();
+    for attr in attrs {
+        if let Meta::List(list) = &attr.meta {
+            if list.path.is_ident("debug_value") {
+                if list.tokens.is_empty() {
+                    return Err(syn::Error::new(
+                        attr.meta.span(),
+                        "expected expression in #[debug_value(...)]",
+                    ));
+                }
+                field_attrs.value = Some(FieldValue::Expr(list.tokens.clone()));
+                continue;
+            }
+        }
+        if attr.path().is_ident("debug_truncate") {
+            field_attrs.truncate = match parse_truncate(attr)? {
+                TruncateDefault {
+                    max_len: Some(v),
+                    max_items: None,
+                } => Some(Truncate::MaxLen(v)),
+                TruncateDefault {
+                    max_len: None,
+                    max_items: Some(v),
+                } => Some(Truncate::MaxItems(v)),
+                _ => {
+                    return Err(syn::Error::new(
+                        attr.meta.span(),
+                        "set only one of max_len and max_items on a field",
+                    ));
+                }
+            };
+            continue;
+        }
+        if attr.path().is_ident("debug_mask") {
+            let mut keep_last: Option<usize> = None;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("keep_last") {
+                    let value: LitInt = meta.value()?.parse()?;
+                    keep_last = Some(value.base10_parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown debug_mask key, available keys: keep_last"))
+                }
+            })?;
+            match keep_last {
+                // Keeping nothing is the same as `#[debug_redact]`.
+                Some(0) => field_attrs.redact = Some(Redact::All),
+                Some(v) => field_attrs.redact = Some(Redact::KeepLast(v)),
+                None => {
+                    return Err(syn::Error::new(
+                        attr.meta.span(),
+                        "expected #[debug_mask(keep_last = ...)]",
+                    ));
+                }
+            }
+        } else if let Attribute {
+            meta:
+                Meta::NameValue(MetaNameValue {
+                    path,
+                    value:
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(token, ..),
+                            ..
+                        }),
+                    ..
+                }),
+            ..
+        } = &attr
+        {
+            if path.segments.is_empty() {
+                continue;
+            }
+
+            match path.segments.last().unwrap().ident.to_string().as_str() {
+                "debug_name" => {
+                    field_attrs.name =
+                        Some(token.token().to_string().trim_matches('"').to_string());
+                }
+                "debug_value" => {
+                    field_attrs.value = Some(FieldValue::Str(
+                        token.token().to_string().trim_matches('"').to_string(),
+                    ));
+                }
+                "debug_with" => field_attrs.with = Some(token.parse()?),
+                "debug_skip_if" => field_attrs.skip_if = Some(token.parse()?),
+                _ => continue,
+            }
+        } else if let Attribute {
+            meta: Meta::Path(path),
+            ..
+        } = &attr
+        {
+            match path.segments.last().unwrap().ident.to_string().as_str() {
+                "debug_ignore" => field_attrs.ignore = true,
+                "debug_redact" => field_attrs.redact = Some(Redact::All),
+                "debug_display" => field_attrs.format = Some(DebugFormat::Display),
+                "debug_debug" => field_attrs.format = Some(DebugFormat::Debug),
+                "debug_debug_not_pretty" => field_attrs.format = Some(DebugFormat::DebugNotPretty),
+                _ => continue,
+            }
+        }
+    }
+    Ok(field_attrs)
+}
+
 #[allow(clippy::too_many_lines)]
 fn auto_debug_enum(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
+    let mut debug_style: Option<DebugStyle> = None;
     let mut debug_format = DebugFormat::Debug;
 
     let target_ident = &ast.ident;
@@ -229,8 +728,21 @@ fn auto_debug_enum(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
 
     let mut variant_vec: Vec<proc_macro2::TokenStream> = vec![];
 
+    // Whether `AutoDebugFn` is used by any variant.
+    let mut use_fn_wrapper = false;
+
+    // `#[debug_truncate(...)]` on enum.
+    let mut truncate_default = TruncateDefault::default();
+
     // Check attributes on struct.
     for attr in &ast.attrs {
+        if attr.path().is_ident("debug_truncate") {
+            truncate_default = match parse_truncate(attr) {
+                Ok(v) => v,
+                Err(e) => return e.to_compile_error().into(),
+            };
+            continue;
+        }
         if let Attribute {
             meta:
                 Meta::NameValue(MetaNameValue {
@@ -250,6 +762,14 @@ fn auto_debug_enum(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
             }
 
             match path.segments.last().unwrap().ident.to_string().as_str() {
+                "debug_style" => {
+                    debug_style = match check_debug_style(token.token().to_string().as_str()) {
+                        Some(v) => Some(v),
+                        None => {
+                            return compiling_error!(token.span(), "invalid debug_style");
+                        }
+                    }
+                }
                 "debug_format" => {
                     debug_format = match check_debug_format(token.token().to_string().as_str()) {
                         Some(v) => v,
@@ -264,82 +784,135 @@ fn auto_debug_enum(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
     }
 
     for variant in &data_enum.variants {
-        let mut variant_ignore = false;
-        let mut variant_format: Option<DebugFormat> = None;
-
         let variant_ident = &variant.ident;
-        let variant_ident_str = variant_ident.to_string();
-
-        // Check field attr.
-        for attr in &variant.attrs {
-            if let Attribute {
-                meta: Meta::Path(path),
-                ..
-            } = &attr
-            {
-                match path.segments.last().unwrap().ident.to_string().as_str() {
-                    "debug_ignore" => variant_ignore = true,
-                    "debug_display" => variant_format = Some(DebugFormat::Display),
-                    "debug_debug" => variant_format = Some(DebugFormat::Debug),
-                    "debug_debug_not_pretty" => variant_format = Some(DebugFormat::DebugNotPretty),
-                    _ => continue,
-                }
-            }
-        }
 
-        if variant_ignore {
-            continue;
+        // Variants share attributes with fields: `debug_name`, `debug_ignore` and formats.
+        if let Err(e) = check_variant_attrs(&variant.attrs) {
+            return e.to_compile_error().into();
         }
-
-        let variant_placeholder = match variant_format {
-            Some(DebugFormat::Debug) => PLACEHOLDER_DEBUG,
-            Some(DebugFormat::Display) => PLACEHOLDER_DISPLAY,
-            Some(DebugFormat::DebugNotPretty) => PLACEHOLDER_DEBUG_NOT_PRETTY,
-            None => match debug_format {
-                DebugFormat::Debug => PLACEHOLDER_DEBUG,
-                DebugFormat::Display => PLACEHOLDER_DISPLAY,
-                DebugFormat::DebugNotPretty => PLACEHOLDER_DEBUG_NOT_PRETTY,
-            },
+        let variant_attrs = match parse_field_attrs(&variant.attrs) {
+            Ok(v) => v,
+            Err(e) => return e.to_compile_error().into(),
         };
 
+        let variant_ident_str = variant_attrs
+            .name
+            .unwrap_or_else(|| variant_ident.to_string());
+
+        let variant_format = resolve_format(variant_attrs.format, debug_format);
+
         let stmt = match &variant.fields {
-            Fields::Unit => quote! {
-                #target_ident::#variant_ident => f.write_str(format!(#variant_placeholder, #variant_ident_str).as_str())
+            // Ignored variants print their name only, the arm is kept so the match is exhaustive.
+            _ if variant_attrs.ignore => quote! {
+                #target_ident::#variant_ident { .. } => f.write_str(#variant_ident_str)
             },
-            Fields::Unnamed(_) => quote! {
-                // This branch is for enum variants that have a unnamed type:
-                // Foo(MyType)
-                #target_ident::#variant_ident(vv) => f.debug_tuple(#variant_ident_str).field(&format_args!(#variant_placeholder, vv)).finish()
+            Fields::Unit => quote! {
+                #target_ident::#variant_ident => f.write_str(#variant_ident_str)
             },
-            Fields::Named(fields_names) => {
-                // This branch is for enum variants that have a struct:
+            Fields::Unnamed(_) | Fields::Named(_) => {
+                // This branch is for enum variants that have unnamed types or a struct:
+                // Foo(MyType, u8)
                 // Foo{a: i32, b: u32}
 
-                // Left side of arm statement, each element is a "field_name: v1"
-                // Test::T4 { a: __self_0, b: __self_1 } =>
+                // Tuple variants print in tuple style and struct variants print in struct style
+                // unless `debug_style` is set on the enum.
+                let variant_style = debug_style.unwrap_or(match &variant.fields {
+                    Fields::Unnamed(_) => DebugStyle::Tuple,
+                    _ => DebugStyle::Struct,
+                });
+
+                // Left side of arm statement, each element is a "field_name: v0" or a "v0", fields
+                // that are ignored, redacted or have `debug_value = "..."` are not bound unless
+                // they have `debug_skip_if`.
+                // Test::T2(v0, _, v2) =>
+                // Test::T4 { a: v0, b: v1, .. } =>
                 let mut field_left_vec: Vec<proc_macro2::TokenStream> = vec![];
 
-                // Right side of arm statement, each element is a "key(name).value(value)"
-                // f.debug_map(field_name).key(field_1).value(v1).key(field_2).value(v2). ... .finish()
+                // Right side of arm statement, each element is a "ff.field(name, value);",
+                // "ff.field(value);" or "ff.key(name).value(value);" according to style.
+                // {
+                //     let mut ff = f.debug_struct(variant_name);
+                //     ff.field(field_1, v0);
+                //     if !skip(v1) {
+                //         ff.field(field_2, v1);
+                //     }
+                //     ff.finish()
+                // }
                 let mut field_right_vec: Vec<proc_macro2::TokenStream> = vec![];
-                for (field_index, field) in fields_names.named.iter().enumerate() {
-                    let name = &field.ident.as_ref().unwrap();
-                    let name_str = name.to_string();
-
-                    // Fill left side.
+                for (field_index, field) in variant.fields.iter().enumerate() {
+                    let field_attrs = match parse_field_attrs(&field.attrs) {
+                        Ok(v) => v,
+                        Err(e) => return e.to_compile_error().into(),
+                    };
                     let fill_ident = Ident::new(format!("v{field_index}").as_str(), field.span());
-                    field_left_vec.push(quote! {
-                        #name: #fill_ident
-                    });
+                    let bind = !field_attrs.ignore
+                        && (field_attrs.skip_if.is_some()
+                            || !matches!(field_attrs.value, Some(FieldValue::Str(_)))
+                                && !matches!(field_attrs.redact, Some(Redact::All)));
+
+                    match (&field.ident, bind) {
+                        (Some(name), true) => field_left_vec.push(quote! {#name: #fill_ident}),
+                        (Some(_), false) => {}
+                        (None, true) => field_left_vec.push(quote! {#fill_ident}),
+                        (None, false) => field_left_vec.push(quote! {_}),
+                    }
+
+                    if field_attrs.ignore {
+                        continue;
+                    }
 
-                    // Fill right side.
-                    field_right_vec.push(quote! {
-                        key(&format_args!("{}", #name_str)).value(#fill_ident)
+                    let name_str = field_attrs.name.clone().unwrap_or_else(|| {
+                        field
+                            .ident
+                            .as_ref()
+                            .map_or_else(|| field_index.to_string(), ToString::to_string)
                     });
+
+                    let field_value = field_value_expr(
+                        &field_attrs.value,
+                        &quote! {#fill_ident},
+                        &field_binding(field, field_index),
+                    );
+                    let field_value = field_value_tokens(
+                        &field_value,
+                        &field_attrs,
+                        field_attrs.format.or(variant_format),
+                        resolve_truncate(&field_attrs, &field.ty, truncate_default),
+                        None,
+                        &mut use_fn_wrapper,
+                    );
+
+                    let field_stmt = match variant_style {
+                        DebugStyle::Struct => quote! { ff.field(#name_str, #field_value); },
+                        DebugStyle::Tuple => quote! { ff.field(#field_value); },
+                        DebugStyle::Map => {
+                            quote! { ff.key(&format_args!("{}", #name_str)).value(#field_value); }
+                        }
+                    };
+                    field_right_vec.push(skip_if_wrap(
+                        field_stmt,
+                        &field_attrs.skip_if,
+                        &quote! {#fill_ident},
+                    ));
                 }
 
+                let field_left = match &variant.fields {
+                    Fields::Unnamed(_) => quote! { (#(#field_left_vec),*) },
+                    _ => quote! { {#(#field_left_vec,)* ..} },
+                };
+
+                let builder = match variant_style {
+                    DebugStyle::Struct => quote! { f.debug_struct(#variant_ident_str) },
+                    DebugStyle::Tuple => quote! { f.debug_tuple(#variant_ident_str) },
+                    DebugStyle::Map => quote! { f.debug_map() },
+                };
+
                 quote! {
-                    #target_ident::#variant_ident{#(#field_left_vec),*} => f.debug_map().#(#field_right_vec).*.finish()
+                    #target_ident::#variant_ident #field_left => {
+                        let mut ff = #builder;
+                        #(#field_right_vec)*
+                        ff.finish()
+                    }
                 }
             }
         };
@@ -347,9 +920,16 @@ fn auto_debug_enum(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
         variant_vec.push(stmt);
     }
 
+    let fn_wrapper = if use_fn_wrapper {
+        fn_wrapper_def()
+    } else {
+        quote! {}
+    };
+
     let expand = quote! {
         impl #impl_generics std::fmt::Debug for #target_ident #ty_generics #where_clause {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                #fn_wrapper
                 match self {
                     #(#variant_vec,)*
                 }
@@ -364,6 +944,7 @@ fn check_debug_style(style: &str) -> Option<DebugStyle> {
     match style.trim_matches('"') {
         "struct" => Some(DebugStyle::Struct),
         "tuple" => Some(DebugStyle::Tuple),
+        "map" => Some(DebugStyle::Map),
         _ => None,
     }
 }
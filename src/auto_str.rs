@@ -1,16 +1,22 @@
 use proc_macro::TokenStream;
 
-use proc_macro2::TokenTree;
 use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, FieldsUnnamed, Lit,
-    Meta, MetaList, MetaNameValue,
+    parse_macro_input, Attribute, Data, DataStruct, DeriveInput, Expr, ExprLit, Fields,
+    FieldsUnnamed, Ident, Lit, LitChar, LitStr, Meta, MetaNameValue, Path, Token, Type, TypePath,
+    Variant,
 };
 
-use crate::util::{
-    compiling_error, to_camel_case, to_pascal_case, to_screaming_case, to_snake_case,
+use racros_core::{
+    to_camel_case_with, to_pascal_case_with, to_screaming_case_with, to_snake_case_with,
+    CaseOptions, DigitMode,
 };
 
+use crate::util::compiling_error;
+
 #[derive(Debug)]
 enum Rules {
     Lowercase,
@@ -21,79 +27,305 @@ enum Rules {
     ScreamingCase,
 }
 
+/// Rule in `#[autorule = "..."]` or `#[autorule(rule = "...", ...)]`.
+#[derive(Debug)]
+struct AutoRule {
+    rule: Rules,
+    case_options: CaseOptions,
+}
+
+/// Optional helpers enabled by `#[auto_str(...)]` on the enum.
+#[derive(Debug, Default)]
+struct Options {
+    /// `#[auto_str(round_trip)]`: generate `check_round_trip`.
+    round_trip: bool,
+    /// `#[auto_str(list)]`: generate `parse_list`, `parse_list_unique` and `join`.
+    list: bool,
+    /// `#[auto_str(const_fn)]`: generate `from_str_const`.
+    const_fn: bool,
+    /// `#[auto_str(json_schema)]`: generate `json_schema`.
+    json_schema: bool,
+}
+
+/// How the value of a wrapped variant or newtype struct is converted from string.
+#[derive(Debug, Clone, Copy)]
+enum Convert {
+    /// `TryFrom<&str>` on the wrapped type.
+    TryFrom,
+    /// `str::parse`, i.e. `FromStr` on the wrapped type.
+    Parse,
+}
+
+/// Parsed `#[str(...)]` on a variant.
+#[derive(Debug, Default)]
+struct VariantStr {
+    /// Strings to convert from/to, `None` if not set.
+    names: Option<Vec<String>>,
+    /// `convert = "..."`, only for wrapped variants.
+    convert: Option<Convert>,
+    /// Strings in `#[str(...)]` that have `since` or `until`, with their version window.
+    name_window_vec: Vec<(String, VersionWindow)>,
+    /// `since` or `until` in `#[str(...)]` without strings, applies to the whole variant.
+    window: Option<VersionWindow>,
+}
+
+/// Versions that a string is valid in, `since` is inclusive and `until` is exclusive.
+#[derive(Debug, Clone, Default)]
+struct VersionWindow {
+    since: Option<(String, Vec<u64>)>,
+    until: Option<(String, Vec<u64>)>,
+}
+
+impl VersionWindow {
+    /// Statements returning `Err` when `version` (a parsed `Vec<u64>` in generated code) is out
+    /// of this window.
+    fn check_tokens(&self) -> proc_macro2::TokenStream {
+        let check_since = self.since.as_ref().map(|(since_str, since)| {
+            quote! {
+                if compare_version(&version_vec, &[#(#since),*]) == std::cmp::Ordering::Less {
+                    return Err(format!("\"{}\" is only available since version {}, got version {}", value, #since_str, version));
+                }
+            }
+        });
+        let check_until = self.until.as_ref().map(|(until_str, until)| {
+            quote! {
+                if compare_version(&version_vec, &[#(#until),*]) != std::cmp::Ordering::Less {
+                    return Err(format!("\"{}\" is no longer available since version {}, got version {}", value, #until_str, version));
+                }
+            }
+        });
+        quote! {
+            #check_since
+            #check_until
+        }
+    }
+}
+
+/// Argument in `#[str(...)]`.
+enum StrArg {
+    /// `"value"`, string to convert from/to.
+    Value(LitStr),
+    /// `key = "value"`.
+    Key(Ident, LitStr),
+}
+
+impl Parse for StrArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(Ident) {
+            return Ok(Self::Value(parse_str_lit(input)?));
+        }
+        let key: Ident = input.parse()?;
+        if !input.peek(Token![=]) {
+            return Err(syn::Error::new(
+                key.span(),
+                format!(
+                    "expected string literal or `key = \"value\"` in #[str(...)], found `{key}`"
+                ),
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(Self::Key(key, parse_str_lit(input)?))
+    }
+}
+
+/// Parse a plain string literal, reject other literals and raw strings.
+fn parse_str_lit(input: ParseStream) -> syn::Result<LitStr> {
+    match input.parse::<Lit>()? {
+        Lit::Str(v) => {
+            if v.token().to_string().starts_with('r') {
+                return Err(syn::Error::new(
+                    v.span(),
+                    "raw string literal is not supported in #[str(...)]",
+                ));
+            }
+            Ok(v)
+        }
+        v => Err(syn::Error::new(
+            v.span(),
+            "expected string literal in #[str(...)]",
+        )),
+    }
+}
+
+/// Parse all arguments in a `#[str(...)]` attribute, which must not be empty.
+fn parse_str_attr(attr: &Attribute) -> syn::Result<Vec<StrArg>> {
+    let list = match &attr.meta {
+        Meta::List(v) => v,
+        _ => {
+            return Err(syn::Error::new(
+                attr.meta.span(),
+                "expected #[str(\"...\")] with at least one string",
+            ));
+        }
+    };
+    let args = list.parse_args_with(Punctuated::<StrArg, Token![,]>::parse_terminated)?;
+    if args.is_empty() {
+        return Err(syn::Error::new(
+            list.delimiter.span().join(),
+            "empty #[str()], expected at least one string",
+        ));
+    }
+    Ok(args.into_iter().collect())
+}
+
 pub fn auto_str_internal(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     // println!(">>>> ast: {:#?}", &ast);
-    if let Data::Enum(_) = &ast.data {
-    } else {
+    if let Data::Union(_) = &ast.data {
         return compiling_error!(
             proc_macro2::Span::call_site(),
-            "#[derive(TryStrFrom)] only support enums"
+            "#[derive(AutoStr)] only support enums and structs"
         );
     };
     // Check default name format:
-    // check `#[autorule = "xxx"]`:
-    // * Available: lowercase, UPPERCASE, camelCase, PascalCase.
+    // check `#[autorule = "xxx"]` or `#[autorule(rule = "xxx", digits = "xxx")]`:
+    // * Available: lowercase, UPPERCASE, camelCase, PascalCase, snake_case, SCREAMING_CASE.
     // * When not set, use the field ident originally.
-    let mut rule: Option<Rules> = None;
+    let mut rule: Option<AutoRule> = None;
 
     for attr in &ast.attrs {
-        if let Meta::NameValue(MetaNameValue {
-            path,
-            value:
-                Expr::Lit(ExprLit {
-                    lit: Lit::Str(token, ..),
-                    ..
-                }),
-            ..
-        }) = &attr.meta
-        {
-            if path.segments.last().unwrap().ident != "autorule" {
-                continue;
-            }
+        if !attr.path().is_ident("autorule") {
+            continue;
+        }
 
-            rule = match token.token().to_string().trim_matches('"') {
-                "lowercase" => Some(Rules::Lowercase),
-                "UPPERCASE" => Some(Rules::Uppercase),
-                "camelCase" => Some(Rules::CamelCase),
-                "PascalCase" => Some(Rules::PascalCase),
-                "snake_case" => Some(Rules::SnakeCase),
-                "SCREAMING_CASE" => Some(Rules::ScreamingCase),
-                _ => {
-                    return compiling_error!(
-                        token.span(),
-                        "unknown AutoStr rules type: {}",
-                        token.token()
-                    );
-                }
-            };
-            break;
+        rule = match parse_autorule(attr) {
+            Ok(v) => Some(v),
+            Err(e) => return e,
+        };
+        break;
+    }
+
+    // Check optional helpers:
+    // `#[auto_str(round_trip, list, const_fn, json_schema)]`.
+    let mut options = Options::default();
+
+    for attr in &ast.attrs {
+        if !attr.path().is_ident("auto_str") {
+            continue;
+        }
+        if let Err(e) = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("round_trip") {
+                options.round_trip = true;
+                Ok(())
+            } else if meta.path.is_ident("list") {
+                options.list = true;
+                Ok(())
+            } else if meta.path.is_ident("const_fn") {
+                options.const_fn = true;
+                Ok(())
+            } else if meta.path.is_ident("json_schema") {
+                options.json_schema = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown AutoStr option"))
+            }
+        }) {
+            return e.to_compile_error().into();
         }
     }
 
     let mut expand = TokenStream::new();
 
-    let try_from_stream = match generate_try_from(&ast, &rule) {
-        Ok(v) => v,
-        Err(e) => {
-            return e;
+    if let Data::Struct(data_struct) = &ast.data {
+        if options.round_trip || options.const_fn || options.json_schema {
+            return compiling_error!(
+                proc_macro2::Span::call_site(),
+                "#[auto_str(round_trip, const_fn, json_schema)] only support enums"
+            );
         }
-    };
-    expand.extend(try_from_stream);
+        let struct_stream = match generate_struct(&ast, data_struct, &rule) {
+            Ok(v) => v,
+            Err(e) => {
+                return e;
+            }
+        };
+        expand.extend(struct_stream);
+    } else {
+        let try_from_stream = match generate_try_from(&ast, &rule) {
+            Ok(v) => v,
+            Err(e) => {
+                return e;
+            }
+        };
+        expand.extend(try_from_stream);
+
+        let to_string_stream = match generate_to_string(&ast, &rule) {
+            Ok(v) => v,
+            Err(e) => {
+                return e;
+            }
+        };
+        expand.extend(to_string_stream);
+    }
+
+    if options.round_trip {
+        let round_trip_stream = match generate_round_trip(&ast) {
+            Ok(v) => v,
+            Err(e) => {
+                return e;
+            }
+        };
+        expand.extend(round_trip_stream);
+    }
+
+    if options.list {
+        expand.extend(generate_list(&ast));
+    }
 
-    let to_string_stream = match generate_to_string(&ast, &rule) {
-        Ok(v) => v,
+    if options.const_fn {
+        let const_fn_stream = match generate_const_fn(&ast, &rule) {
+            Ok(v) => v,
+            Err(e) => {
+                return e;
+            }
+        };
+        expand.extend(const_fn_stream);
+    }
+
+    if options.json_schema {
+        let json_schema_stream = match generate_json_schema(&ast, &rule) {
+            Ok(v) => v,
+            Err(e) => {
+                return e;
+            }
+        };
+        expand.extend(json_schema_stream);
+    }
+
+    match generate_version(&ast) {
+        Ok(Some(v)) => expand.extend(v),
+        Ok(None) => {}
         Err(e) => {
             return e;
         }
-    };
-    expand.extend(to_string_stream);
+    }
+
+    if let Data::Enum(data_enum) = &ast.data {
+        let has_char_attr = data_enum.variants.iter().any(|variant| {
+            variant
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("char"))
+        });
+        if has_char_attr {
+            let char_stream = match generate_char(&ast, &rule) {
+                Ok(v) => v,
+                Err(e) => {
+                    return e;
+                }
+            };
+            expand.extend(char_stream);
+        }
+    }
 
     expand
 }
 
 #[allow(clippy::too_many_lines)]
-fn generate_try_from(ast: &DeriveInput, rule: &Option<Rules>) -> Result<TokenStream, TokenStream> {
+fn generate_try_from(
+    ast: &DeriveInput,
+    rule: &Option<AutoRule>,
+) -> Result<TokenStream, TokenStream> {
     let target_ident = &ast.ident;
 
     let mut try_from_arm_vec: Vec<proc_macro2::TokenStream> = vec![];
@@ -112,98 +344,76 @@ fn generate_try_from(ast: &DeriveInput, rule: &Option<Rules>) -> Result<TokenStr
     for variant in &data_enum.variants {
         let field_ident = &variant.ident;
 
-        let mut has_str_attr = false;
+        let variant_str = variant_str_attr(variant)?;
 
-        for attr in &variant.attrs {
-            if let Attribute {
-                meta: Meta::List(MetaList { path, tokens, .. }),
-                ..
-            } = &attr
+        if let Some(names_vec) = &variant_str.names {
+            let try_from_arm_result = if let Fields::Unnamed(FieldsUnnamed { unnamed, .. }) =
+                &variant.fields
             {
-                if path.segments.last().unwrap().ident != "str" {
-                    continue;
-                }
-                has_str_attr = true;
-                let mut names_vec = vec![];
-                tokens.clone().into_iter().for_each(|x| {
-                    if let TokenTree::Literal(lit) = &x {
-                        names_vec.push(String::from(lit.to_string().trim_matches('"')));
-                    }
-                });
-
-                let try_from_arm_result = if let Fields::Unnamed(FieldsUnnamed {
-                    unnamed, ..
-                }) = &variant.fields
-                {
-                    let f = unnamed.first().unwrap();
-                    let wrapped_type = &f.ty;
-                    // enum MyEnum {
-                    //     #[str=("e1", "e2")]
-                    //     E1(MyEnum2),
-                    //     E2,
-                    // }
-                    // "e1, e2" => MyEnum::try_from("e1" or "e2")
-                    let mut tmp_vec: Vec<proc_macro2::TokenStream> = vec![];
+                let f = unnamed.first().unwrap();
+                let convert_expr = convert_from_value(&f.ty, variant_str.convert);
+                // enum MyEnum {
+                //     #[str=("e1", "e2")]
+                //     E1(MyEnum2),
+                //     E2,
+                // }
+                // "e1, e2" => MyEnum::try_from("e1" or "e2")
+                let mut tmp_vec: Vec<proc_macro2::TokenStream> = vec![];
 
-                    // `value` is the name of arg in `try_from` function signature.
-                    // Here should use `value` instead of `#name` (element in names_vec)
-                    //
-                    // because we want:
-                    // "e1" | "e2" => {
-                    //     if let Ok(v) = MyEnum::try_from(value) {
-                    //         Ok(MyEnum2::E1(v))
-                    //     }
-                    // }
-                    //
-                    // not:
-                    //
-                    // "e1" | "e2" => {
-                    //     if let Ok(v) = MyEnum::try_from("e1") {
-                    //         Ok(MyEnum2::E1(v))
-                    //     }
-                    //     if let Ok(v) = MyEnum::try_from("e2") {
-                    //         Ok(MyEnum2::E1(v))
-                    //     }
-                    // }
-                    //
-                    let target_name_str_ident = target_ident.to_string();
-                    tmp_vec.push(quote! {
-                        match #wrapped_type::try_from(value) {
-                            Ok(v) => Ok(#target_ident::#field_ident(v)),
-                            Err(e) => Err(format!("failed to convert to {}: {}", #target_name_str_ident, e))
-                        }
-                    });
-                    tmp_vec.push(quote! {});
-                    quote! {#(#tmp_vec)*}
-                } else {
-                    quote! {
-                        Ok(#target_ident::#field_ident)
-                    }
-                };
-                // Add {} around `#try_from_arm_result`, otherwise the compilers seems treating the
-                // `if let` statements inside as a serial of sentences, not a block.
-                // And that {} can not work if add in names_vec, must add here, where expands.
+                // `value` is the name of arg in `try_from` function signature.
+                // Here should use `value` instead of `#name` (element in names_vec)
                 //
-                // Actually we do not need {} here, because the right side of match arm becomes
-                // a single match, not a serial of `if let`.
-                try_from_arm_vec.push(quote! {
-                    // #(#names_vec)|* => {#try_from_arm_result}
-                    #(#names_vec)|* => #try_from_arm_result
+                // because we want:
+                // "e1" | "e2" => {
+                //     if let Ok(v) = MyEnum::try_from(value) {
+                //         Ok(MyEnum2::E1(v))
+                //     }
+                // }
+                //
+                // not:
+                //
+                // "e1" | "e2" => {
+                //     if let Ok(v) = MyEnum::try_from("e1") {
+                //         Ok(MyEnum2::E1(v))
+                //     }
+                //     if let Ok(v) = MyEnum::try_from("e2") {
+                //         Ok(MyEnum2::E1(v))
+                //     }
+                // }
+                //
+                let target_name_str_ident = target_ident.to_string();
+                tmp_vec.push(quote! {
+                    match #convert_expr {
+                        Ok(v) => Ok(#target_ident::#field_ident(v)),
+                        Err(e) => Err(format!("failed to convert to {}: {}", #target_name_str_ident, e))
+                    }
                 });
-
-                break;
-            }
-        }
-
-        if !has_str_attr {
+                tmp_vec.push(quote! {});
+                quote! {#(#tmp_vec)*}
+            } else {
+                quote! {
+                    Ok(#target_ident::#field_ident)
+                }
+            };
+            // Add {} around `#try_from_arm_result`, otherwise the compilers seems treating the
+            // `if let` statements inside as a serial of sentences, not a block.
+            // And that {} can not work if add in names_vec, must add here, where expands.
+            //
+            // Actually we do not need {} here, because the right side of match arm becomes
+            // a single match, not a serial of `if let`.
+            try_from_arm_vec.push(quote! {
+                // #(#names_vec)|* => {#try_from_arm_result}
+                #(#names_vec)|* => #try_from_arm_result
+            });
+        } else {
             // Do not have a #[str(..)] on this field.
             // Convert from/to string with rule.
             if let Fields::Unnamed(FieldsUnnamed { unnamed, .. }) = &variant.fields {
                 let f = unnamed.first().unwrap();
-                let wrapped_type = &f.ty;
+                let convert_expr = convert_from_value(&f.ty, variant_str.convert);
                 let wrapped_type_str = f.ty.to_token_stream().to_string();
                 try_from_guess_vec.push(quote! {
-                    if let Ok(v) = #wrapped_type::try_from(value) {
+                    if let Ok(v) = #convert_expr {
                         if fallback_result.is_some() {
                             return Err(Self::Error::from(format!("#[str(...)] attribute not set and fallback guess is ambiguous: both {} and {} can accept this convert from \"{}\"", fallback_field.unwrap(), #wrapped_type_str, value)));
                         }
@@ -258,7 +468,10 @@ fn generate_try_from(ast: &DeriveInput, rule: &Option<Rules>) -> Result<TokenStr
     Ok(expand.into())
 }
 
-fn generate_to_string(ast: &DeriveInput, rule: &Option<Rules>) -> Result<TokenStream, TokenStream> {
+fn generate_to_string(
+    ast: &DeriveInput,
+    rule: &Option<AutoRule>,
+) -> Result<TokenStream, TokenStream> {
     // println!(">>>> ast: {:#?}", &ast);
     let data_enum = match &ast.data {
         Data::Enum(v) => v,
@@ -277,30 +490,8 @@ fn generate_to_string(ast: &DeriveInput, rule: &Option<Rules>) -> Result<TokenSt
     for variant in &data_enum.variants {
         let field_ident = &variant.ident;
 
-        let mut to_string_target = String::new();
-        let mut has_str_attr = false;
-
-        for attr in &variant.attrs {
-            if let Attribute {
-                meta: Meta::List(MetaList { path, tokens, .. }),
-                ..
-            } = &attr
-            {
-                if path.segments.last().unwrap().ident != "str" {
-                    continue;
-                }
-                has_str_attr = true;
-                to_string_target =
-                    if let Some(TokenTree::Literal(lit)) = &tokens.clone().into_iter().next() {
-                        String::from(lit.to_string().trim_matches('"'))
-                    } else {
-                        variant.ident.to_string().clone()
-                    };
-                break;
-            }
-        }
-
-        if has_str_attr {
+        if let Some(names_vec) = variant_str_attr(variant)?.names {
+            let to_string_target = &names_vec[0];
             match &variant.fields {
                 Fields::Unit => {
                     // enum MyEnum {
@@ -355,14 +546,975 @@ fn generate_to_string(ast: &DeriveInput, rule: &Option<Rules>) -> Result<TokenSt
     Ok(expand.into())
 }
 
-fn string_target_with_rule(rule: &Option<Rules>, str: &str) -> String {
+/// Generate `check_round_trip`, which converts every unit variant to string and back.
+///
+/// `try_from` and `to_string` are generated separately, so a variant may render to a string
+/// that is claimed by another variant (e.g. an alias in `#[str(...)]` of a wrapped variant).
+/// Such "one-way" variants are reported by name.
+fn generate_round_trip(ast: &DeriveInput) -> Result<TokenStream, TokenStream> {
+    let data_enum = match &ast.data {
+        Data::Enum(v) => v,
+        _ => {
+            return Err(compiling_error!(
+                proc_macro2::Span::call_site(),
+                "#[derive(AutoStr)] only support enums"
+            ));
+        }
+    };
+
+    let target_ident = &ast.ident;
+
+    let mut check_vec: Vec<proc_macro2::TokenStream> = vec![];
+
+    for variant in &data_enum.variants {
+        // Wrapped variants can not be constructed without a value, only check unit ones.
+        if !matches!(variant.fields, Fields::Unit) {
+            continue;
+        }
+        let field_ident = &variant.ident;
+        let field_ident_str = field_ident.to_string();
+        check_vec.push(quote! {
+            (
+                #field_ident_str,
+                matches!(
+                    Self::try_from(#target_ident::#field_ident.to_string().as_str()),
+                    Ok(#target_ident::#field_ident)
+                ),
+            )
+        });
+    }
+
+    let check_count = check_vec.len();
+
+    let expand = quote! {
+        impl #target_ident {
+            /// Check that every unit variant converts back to itself through `to_string` and
+            /// `try_from`.
+            ///
+            /// Return the names of variants that do not.
+            pub fn check_round_trip() -> Result<(), Vec<&'static str>> {
+                let checks: [(&'static str, bool); #check_count] = [#(#check_vec),*];
+                let one_way_vec = checks
+                    .into_iter()
+                    .filter(|(_, ok)| !ok)
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>();
+                if one_way_vec.is_empty() {
+                    Ok(())
+                } else {
+                    Err(one_way_vec)
+                }
+            }
+        }
+    };
+
+    Ok(expand.into())
+}
+
+/// Generate `TryFrom<&str>` and `ToString` for structs.
+///
+/// * Unit structs convert from/to the strings in `#[str(...)]`, or the struct name with rule.
+/// * Newtype structs delegate to the wrapped type, with optional `prefix` and `validate`.
+#[allow(clippy::too_many_lines)]
+fn generate_struct(
+    ast: &DeriveInput,
+    data_struct: &DataStruct,
+    rule: &Option<AutoRule>,
+) -> Result<TokenStream, TokenStream> {
+    let target_ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = &ast.generics.split_for_impl();
+    let target_name_str_ident = target_ident.to_string();
+
+    let mut names_vec: Vec<String> = vec![];
+    let mut prefix: Option<LitStr> = None;
+    let mut validate: Option<Path> = None;
+    let mut convert: Option<Convert> = None;
+
+    for attr in &ast.attrs {
+        if !attr.path().is_ident("str") {
+            continue;
+        }
+        let args = parse_str_attr(attr).map_err(|e| TokenStream::from(e.to_compile_error()))?;
+        for arg in args {
+            match arg {
+                StrArg::Value(v) => names_vec.push(v.value()),
+                StrArg::Key(key, value) => match key.to_string().as_str() {
+                    "prefix" => prefix = Some(value),
+                    "convert" => convert = Some(parse_convert(&value)?),
+                    "validate" => {
+                        validate = Some(
+                            value
+                                .parse::<Path>()
+                                .map_err(|e| TokenStream::from(e.to_compile_error()))?,
+                        );
+                    }
+                    _ => {
+                        return Err(compiling_error!(
+                            key.span(),
+                            "unknown AutoStr key: {}, available keys: prefix, validate, convert",
+                            key
+                        ));
+                    }
+                },
+            }
+        }
+    }
+
+    let expand = match &data_struct.fields {
+        Fields::Unit => {
+            if prefix.is_some() || validate.is_some() || convert.is_some() {
+                return Err(compiling_error!(
+                    target_ident.span(),
+                    "prefix, validate and convert only apply to newtype structs"
+                ));
+            }
+            if names_vec.is_empty() {
+                names_vec.push(string_target_with_rule(
+                    rule,
+                    target_ident.to_string().as_str(),
+                ));
+            }
+            let to_string_target = &names_vec[0];
+            quote! {
+                impl #impl_generics TryFrom<&str> for #target_ident #ty_generics #where_clause {
+                    type Error = String;
+
+                    fn try_from(value: &str) -> Result<Self, Self::Error> {
+                        match value {
+                            #(#names_vec)|* => Ok(#target_ident),
+                            _ => Err(format!("failed to convert to {} :invalid value \"{}\"", #target_name_str_ident, value)),
+                        }
+                    }
+                }
+
+                #[allow(clippy::to_string_trait_impl)]
+                impl #impl_generics ToString for #target_ident #ty_generics #where_clause {
+                    fn to_string(&self) -> String {
+                        #to_string_target.to_string()
+                    }
+                }
+            }
+        }
+        Fields::Unnamed(FieldsUnnamed { unnamed, .. }) if unnamed.len() == 1 => {
+            if !names_vec.is_empty() {
+                return Err(compiling_error!(
+                    target_ident.span(),
+                    "string values in #[str(...)] only apply to unit structs, newtype structs delegate to the wrapped type"
+                ));
+            }
+            let convert_expr = convert_from_value(&unnamed.first().unwrap().ty, convert);
+
+            // struct MyId(String);
+            //
+            // With `#[str(prefix = "id-")]`:
+            // "id-foo" => MyId(String::try_from("foo"))
+            let strip_prefix = prefix.as_ref().map(|prefix| {
+                quote! {
+                    let value = match value.strip_prefix(#prefix) {
+                        Some(v) => v,
+                        None => {
+                            return Err(format!("failed to convert to {} :missing prefix \"{}\" in \"{}\"", #target_name_str_ident, #prefix, value));
+                        }
+                    };
+                }
+            });
+            // With `#[str(validate = "path::to::fn")]`, `fn(&str) -> bool` checks value after
+            // prefix stripped.
+            let check_validate = validate.as_ref().map(|validate| {
+                quote! {
+                    if !#validate(value) {
+                        return Err(format!("failed to convert to {} :invalid value \"{}\"", #target_name_str_ident, value));
+                    }
+                }
+            });
+            let to_string_body = match &prefix {
+                Some(prefix) => quote! { format!("{}{}", #prefix, self.0.to_string()) },
+                None => quote! { self.0.to_string() },
+            };
+            quote! {
+                impl #impl_generics TryFrom<&str> for #target_ident #ty_generics #where_clause {
+                    type Error = String;
+
+                    fn try_from(value: &str) -> Result<Self, Self::Error> {
+                        #strip_prefix
+                        #check_validate
+                        match #convert_expr {
+                            Ok(v) => Ok(#target_ident(v)),
+                            Err(e) => Err(format!("failed to convert to {}: {}", #target_name_str_ident, e))
+                        }
+                    }
+                }
+
+                #[allow(clippy::to_string_trait_impl)]
+                impl #impl_generics ToString for #target_ident #ty_generics #where_clause {
+                    fn to_string(&self) -> String {
+                        #to_string_body
+                    }
+                }
+            }
+        }
+        _ => {
+            return Err(compiling_error!(
+                data_struct.fields.span(),
+                "#[derive(AutoStr)] only support newtype structs and unit structs"
+            ));
+        }
+    };
+
+    Ok(expand.into())
+}
+
+/// Generate `parse_list`, `parse_list_unique` and `join` on top of `TryFrom<&str>` and `ToString`.
+///
+/// Items are trimmed before converting, errors carry the index and byte position of the item in
+/// input.
+fn generate_list(ast: &DeriveInput) -> TokenStream {
+    let target_ident = &ast.ident;
+
+    // Loop body shared by `parse_list` and `parse_list_unique`, the latter additionally records
+    // converted values in `duplicate_state` and checks them in `check_duplicate`.
+    let parse_loop = |duplicate_state: proc_macro2::TokenStream,
+                      check_duplicate: proc_macro2::TokenStream| {
+        quote! {
+            if sep.is_empty() {
+                return Err(String::from("failed to parse list: empty separator"));
+            }
+            let mut ret: Vec<Self> = vec![];
+            if input.trim().is_empty() {
+                return Ok(ret);
+            }
+            // Allow one trailing separator like "a,b,".
+            let trimmed = input.trim_end();
+            let input = trimmed.strip_suffix(sep).unwrap_or(trimmed);
+            #duplicate_state
+            let mut position = 0;
+            for (index, item) in input.split(sep).enumerate() {
+                let item_position = position + item.len() - item.trim_start().len();
+                position += item.len() + sep.len();
+                let v = match Self::try_from(item.trim()) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        return Err(format!("failed to parse item {} at position {}: {}", index, item_position, e));
+                    }
+                };
+                #check_duplicate
+                ret.push(v);
+            }
+            Ok(ret)
+        }
+    };
+
+    let parse_list_body = parse_loop(quote! {}, quote! {});
+    let parse_list_unique_body = parse_loop(
+        quote! {
+            let mut names: Vec<(String, usize)> = vec![];
+        },
+        quote! {
+            let name = v.to_string();
+            if let Some((_, first_index)) = names.iter().find(|(n, _)| *n == name) {
+                return Err(format!("duplicate value \"{}\" in item {} at position {}, first seen in item {}", name, index, item_position, first_index));
+            }
+            names.push((name, index));
+        },
+    );
+
+    let expand = quote! {
+        impl #target_ident {
+            /// Parse `input` separated by `sep` into a list of values.
+            ///
+            /// Items are trimmed before converting, an empty `input` gives an empty list and one
+            /// trailing separator is allowed.
+            pub fn parse_list(input: &str, sep: &str) -> Result<Vec<Self>, String> {
+                #parse_list_body
+            }
+
+            /// Same as `parse_list`, but values that convert to the same string (e.g. aliases of
+            /// the same variant) are rejected as duplicate.
+            pub fn parse_list_unique(input: &str, sep: &str) -> Result<Vec<Self>, String> {
+                #parse_list_unique_body
+            }
+
+            /// Join `items` into a string separated by `sep`.
+            pub fn join(items: &[Self], sep: &str) -> String {
+                items
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(sep)
+            }
+        }
+    };
+
+    expand.into()
+}
+
+/// Generate `const fn from_str_const` for enums made of unit variants.
+///
+/// `match` on `&str` is not available in const context, compare bytes manually instead.
+fn generate_const_fn(
+    ast: &DeriveInput,
+    rule: &Option<AutoRule>,
+) -> Result<TokenStream, TokenStream> {
+    let data_enum = match &ast.data {
+        Data::Enum(v) => v,
+        _ => {
+            return Err(compiling_error!(
+                proc_macro2::Span::call_site(),
+                "#[auto_str(const_fn)] only support enums"
+            ));
+        }
+    };
+
+    let target_ident = &ast.ident;
+
+    let mut check_vec: Vec<proc_macro2::TokenStream> = vec![];
+
+    for variant in &data_enum.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(compiling_error!(
+                variant.span(),
+                "#[auto_str(const_fn)] only support enums made of unit variants"
+            ));
+        }
+        let field_ident = &variant.ident;
+        let names_vec = variant_str_attr(variant)?.names.unwrap_or_else(|| {
+            vec![string_target_with_rule(
+                rule,
+                field_ident.to_string().as_str(),
+            )]
+        });
+        check_vec.push(quote! {
+            if #(bytes_eq(bytes, #names_vec.as_bytes()))||* {
+                return Some(#target_ident::#field_ident);
+            }
+        });
+    }
+
+    let expand = quote! {
+        impl #target_ident {
+            /// Convert from string in const context, same as `try_from` but return `None` on
+            /// invalid value.
+            pub const fn from_str_const(s: &str) -> Option<Self> {
+                const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+                    if a.len() != b.len() {
+                        return false;
+                    }
+                    let mut i = 0;
+                    while i < a.len() {
+                        if a[i] != b[i] {
+                            return false;
+                        }
+                        i += 1;
+                    }
+                    true
+                }
+
+                let bytes = s.as_bytes();
+                #(#check_vec)*
+                None
+            }
+        }
+    };
+
+    Ok(expand.into())
+}
+
+/// Generate `TryFrom<char>` and `to_char`, enabled when any variant has `#[char(...)]`.
+///
+/// Variants without `#[char(...)]` use their single-character strings from `#[str(...)]` or rule,
+/// every variant must have at least one character.
+fn generate_char(ast: &DeriveInput, rule: &Option<AutoRule>) -> Result<TokenStream, TokenStream> {
+    let data_enum = match &ast.data {
+        Data::Enum(v) => v,
+        _ => {
+            return Err(compiling_error!(
+                proc_macro2::Span::call_site(),
+                "#[char(...)] only support enums"
+            ));
+        }
+    };
+
+    let target_ident = &ast.ident;
+    let target_name_str_ident = target_ident.to_string();
+
+    let mut try_from_arm_vec: Vec<proc_macro2::TokenStream> = vec![];
+    let mut to_char_arm_vec: Vec<proc_macro2::TokenStream> = vec![];
+
+    for variant in &data_enum.variants {
+        let field_ident = &variant.ident;
+
+        let mut chars_vec: Vec<char> = vec![];
+        for attr in &variant.attrs {
+            if !attr.path().is_ident("char") {
+                continue;
+            }
+            let list = attr
+                .meta
+                .require_list()
+                .map_err(|e| TokenStream::from(e.to_compile_error()))?;
+            let args = list
+                .parse_args_with(Punctuated::<LitChar, Token![,]>::parse_terminated)
+                .map_err(|e| TokenStream::from(e.to_compile_error()))?;
+            if args.is_empty() {
+                return Err(compiling_error!(
+                    list.delimiter.span().join(),
+                    "empty #[char()], expected at least one char"
+                ));
+            }
+            chars_vec.extend(args.iter().map(LitChar::value));
+        }
+
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(compiling_error!(
+                variant.span(),
+                "#[char(...)] only support enums made of unit variants"
+            ));
+        }
+
+        if chars_vec.is_empty() {
+            // Fallback to single-character strings.
+            let names_vec = variant_str_attr(variant)?.names.unwrap_or_else(|| {
+                vec![string_target_with_rule(
+                    rule,
+                    field_ident.to_string().as_str(),
+                )]
+            });
+            chars_vec.extend(names_vec.iter().filter_map(|name| {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(ch), None) => Some(ch),
+                    _ => None,
+                }
+            }));
+        }
+
+        if chars_vec.is_empty() {
+            return Err(compiling_error!(
+                variant.span(),
+                "variant {} has no single-character form, add #[char('...')] to it",
+                field_ident
+            ));
+        }
+
+        let to_char_target = chars_vec[0];
+        try_from_arm_vec.push(quote! {
+            #(#chars_vec)|* => Ok(#target_ident::#field_ident)
+        });
+        to_char_arm_vec.push(quote! {
+            #target_ident::#field_ident => #to_char_target
+        });
+    }
+
+    let expand = quote! {
+        impl TryFrom<char> for #target_ident {
+            type Error = String;
+
+            fn try_from(value: char) -> Result<Self, Self::Error> {
+                match value {
+                    #(#try_from_arm_vec,)*
+                    _ => Err(format!("failed to convert to {} :invalid value '{}'", #target_name_str_ident, value)),
+                }
+            }
+        }
+
+        impl #target_ident {
+            /// Convert to char, the first one in `#[char(...)]` or the single-character string.
+            pub fn to_char(&self) -> char {
+                match self {
+                    #(#to_char_arm_vec,)*
+                }
+            }
+        }
+    };
+
+    Ok(expand.into())
+}
+
+/// Generate `json_schema`, a JSON Schema of all accepted strings, built at compile time.
+///
+/// Each variant is an element in `oneOf`, with its accepted strings in `enum`, the one used in
+/// `to_string` in `x-canonical` and doc comments in `description`. The top level `enum` lists all
+/// accepted strings.
+fn generate_json_schema(
+    ast: &DeriveInput,
+    rule: &Option<AutoRule>,
+) -> Result<TokenStream, TokenStream> {
+    let data_enum = match &ast.data {
+        Data::Enum(v) => v,
+        _ => {
+            return Err(compiling_error!(
+                proc_macro2::Span::call_site(),
+                "#[auto_str(json_schema)] only support enums"
+            ));
+        }
+    };
+
+    let target_ident = &ast.ident;
+
+    let mut all_names_vec: Vec<String> = vec![];
+    let mut one_of_vec: Vec<String> = vec![];
+
+    for variant in &data_enum.variants {
+        let names_vec = match (variant_str_attr(variant)?.names, &variant.fields) {
+            (Some(v), _) => v,
+            (None, Fields::Unit) => vec![string_target_with_rule(
+                rule,
+                variant.ident.to_string().as_str(),
+            )],
+            (None, _) => {
+                // Accepted strings are decided by the wrapped type at runtime.
+                return Err(compiling_error!(
+                    variant.span(),
+                    "#[auto_str(json_schema)] requires #[str(...)] on wrapped variants"
+                ));
+            }
+        };
+
+        let mut entry_vec = vec![format!(
+            "\"title\": {}",
+            json_string(variant.ident.to_string().as_str())
+        )];
+        if let Some(description) = doc_comment(&variant.attrs) {
+            entry_vec.push(format!(
+                "\"description\": {}",
+                json_string(description.as_str())
+            ));
+        }
+        entry_vec.push(format!("\"enum\": {}", json_string_array(&names_vec)));
+        entry_vec.push(format!(
+            "\"x-canonical\": {}",
+            json_string(names_vec[0].as_str())
+        ));
+        one_of_vec.push(format!(
+            "    {{\n      {}\n    }}",
+            entry_vec.join(",\n      ")
+        ));
+
+        all_names_vec.extend(names_vec);
+    }
+
+    let mut schema_vec = vec![
+        String::from("\"$schema\": \"https://json-schema.org/draft/2020-12/schema\""),
+        format!(
+            "\"title\": {}",
+            json_string(target_ident.to_string().as_str())
+        ),
+    ];
+    if let Some(description) = doc_comment(&ast.attrs) {
+        schema_vec.push(format!(
+            "\"description\": {}",
+            json_string(description.as_str())
+        ));
+    }
+    schema_vec.push(String::from("\"type\": \"string\""));
+    schema_vec.push(format!("\"enum\": {}", json_string_array(&all_names_vec)));
+    schema_vec.push(format!("\"oneOf\": [\n{}\n  ]", one_of_vec.join(",\n")));
+
+    let schema = format!("{{\n  {}\n}}", schema_vec.join(",\n  "));
+
+    let expand = quote! {
+        impl #target_ident {
+            /// JSON Schema of all strings accepted by `try_from`.
+            pub fn json_schema() -> &'static str {
+                #schema
+            }
+        }
+    };
+
+    Ok(expand.into())
+}
+
+/// Doc comments in `attrs`, lines trimmed and joined with `\n`.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                path,
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(token),
+                        ..
+                    }),
+                ..
+            }) if path.is_ident("doc") => Some(token.value().trim().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+fn json_string(str: &str) -> String {
+    let mut ret = String::from("\"");
+    for ch in str.chars() {
+        match ch {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if c.is_control() => ret.push_str(format!("\\u{:04x}", c as u32).as_str()),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+fn json_string_array(strs: &[String]) -> String {
+    format!(
+        "[{}]",
+        strs.iter()
+            .map(|v| json_string(v.as_str()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Parse all `#[str(...)]` on the variant.
+fn variant_str_attr(variant: &Variant) -> Result<VariantStr, TokenStream> {
+    let mut variant_str = VariantStr::default();
+    for attr in &variant.attrs {
+        if !attr.path().is_ident("str") {
+            continue;
+        }
+        let args = parse_str_attr(attr).map_err(|e| TokenStream::from(e.to_compile_error()))?;
+        // Strings and version window in this attribute.
+        let mut attr_names_vec: Vec<String> = vec![];
+        let mut window: Option<VersionWindow> = None;
+        for arg in args {
+            match arg {
+                StrArg::Value(v) => attr_names_vec.push(v.value()),
+                StrArg::Key(key, value) if key == "convert" => {
+                    if !matches!(variant.fields, Fields::Unnamed(_)) {
+                        return Err(compiling_error!(
+                            key.span(),
+                            "convert only applies to wrapped variants"
+                        ));
+                    }
+                    variant_str.convert = Some(parse_convert(&value)?);
+                }
+                StrArg::Key(key, value) if key == "since" => {
+                    window.get_or_insert_with(VersionWindow::default).since =
+                        Some((value.value(), parse_version_lit(&value)?));
+                }
+                StrArg::Key(key, value) if key == "until" => {
+                    window.get_or_insert_with(VersionWindow::default).until =
+                        Some((value.value(), parse_version_lit(&value)?));
+                }
+                StrArg::Key(key, _) => {
+                    return Err(compiling_error!(
+                        key.span(),
+                        "unknown AutoStr key on variant: {}, available keys: convert, since, until",
+                        key
+                    ));
+                }
+            }
+        }
+        if let Some(window) = window {
+            if let (Some((since_str, since)), Some((until_str, until))) =
+                (&window.since, &window.until)
+            {
+                if compare_version(since, until) != std::cmp::Ordering::Less {
+                    return Err(compiling_error!(
+                        attr.meta.span(),
+                        "since version {} is not earlier than until version {}",
+                        since_str,
+                        until_str
+                    ));
+                }
+            }
+            if attr_names_vec.is_empty() {
+                variant_str.window = Some(window);
+            } else {
+                variant_str.name_window_vec.extend(
+                    attr_names_vec
+                        .iter()
+                        .map(|name| (name.clone(), window.clone())),
+                );
+            }
+        }
+        if !attr_names_vec.is_empty() {
+            variant_str
+                .names
+                .get_or_insert_with(Vec::new)
+                .extend(attr_names_vec);
+        }
+    }
+    Ok(variant_str)
+}
+
+/// Parse a version like "2.1" into `[2, 1]`.
+fn parse_version_lit(value: &LitStr) -> Result<Vec<u64>, TokenStream> {
+    value
+        .value()
+        .split('.')
+        .map(|v| v.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| {
+            compiling_error!(
+                value.span(),
+                "invalid version {:?}, expected numbers separated by '.'",
+                value.value()
+            )
+        })
+}
+
+/// Compare versions, missing parts are zero: "2.1" equals to "2.1.0".
+fn compare_version(a: &[u64], b: &[u64]) -> std::cmp::Ordering {
+    for i in 0..a.len().max(b.len()) {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        if x != y {
+            return x.cmp(&y);
+        }
+    }
+    std::cmp::Ordering::Equal
+}
+
+/// Generate `try_from_version`, enabled when any `#[str(...)]` has `since` or `until`.
+///
+/// Same as `try_from`, but also rejects strings and variants out of their version window.
+fn generate_version(ast: &DeriveInput) -> Result<Option<TokenStream>, TokenStream> {
+    let data_enum = match &ast.data {
+        Data::Enum(v) => v,
+        _ => return Ok(None),
+    };
+
+    let target_ident = &ast.ident;
+
+    // Checks on the input string, for windows of strings.
+    let mut name_arm_vec: Vec<proc_macro2::TokenStream> = vec![];
+    // Checks on the converted value, for windows of variants.
+    let mut variant_arm_vec: Vec<proc_macro2::TokenStream> = vec![];
+
+    for variant in &data_enum.variants {
+        let field_ident = &variant.ident;
+        let variant_str = variant_str_attr(variant)?;
+
+        for (name, window) in &variant_str.name_window_vec {
+            let check = window.check_tokens();
+            name_arm_vec.push(quote! {
+                if value == #name {
+                    #check
+                }
+            });
+        }
+
+        if let Some(window) = &variant_str.window {
+            let check = window.check_tokens();
+            let pattern = match &variant.fields {
+                Fields::Unit => quote! { #target_ident::#field_ident },
+                Fields::Unnamed(_) => quote! { #target_ident::#field_ident(..) },
+                Fields::Named(_) => quote! { #target_ident::#field_ident { .. } },
+            };
+            variant_arm_vec.push(quote! {
+                if matches!(ret, #pattern) {
+                    #check
+                }
+            });
+        }
+    }
+
+    if name_arm_vec.is_empty() && variant_arm_vec.is_empty() {
+        return Ok(None);
+    }
+
+    let expand = quote! {
+        impl #target_ident {
+            /// Same as `try_from`, but reject strings and variants not available in `version`,
+            /// according to `since` (inclusive) and `until` (exclusive) in `#[str(...)]`.
+            pub fn try_from_version(value: &str, version: &str) -> Result<Self, String> {
+                fn compare_version(a: &[u64], b: &[u64]) -> std::cmp::Ordering {
+                    for i in 0..a.len().max(b.len()) {
+                        let x = a.get(i).copied().unwrap_or(0);
+                        let y = b.get(i).copied().unwrap_or(0);
+                        if x != y {
+                            return x.cmp(&y);
+                        }
+                    }
+                    std::cmp::Ordering::Equal
+                }
+
+                let version_vec = match version
+                    .split('.')
+                    .map(|v| v.parse::<u64>())
+                    .collect::<Result<Vec<u64>, _>>()
+                {
+                    Ok(v) => v,
+                    Err(_) => {
+                        return Err(format!("invalid version \"{}\"", version));
+                    }
+                };
+
+                #(#name_arm_vec)*
+
+                let ret = Self::try_from(value)?;
+
+                #(#variant_arm_vec)*
+
+                Ok(ret)
+            }
+        }
+    };
+
+    Ok(Some(expand.into()))
+}
+
+/// Parse value of `convert = "..."`.
+fn parse_convert(value: &LitStr) -> Result<Convert, TokenStream> {
+    match value.value().as_str() {
+        "try_from" => Ok(Convert::TryFrom),
+        "parse" => Ok(Convert::Parse),
+        v => Err(compiling_error!(
+            value.span(),
+            "unknown convert: {}, available: try_from, parse",
+            v
+        )),
+    }
+}
+
+/// Expression converting `value` into `ty`, returning a `Result`.
+///
+/// When `convert` is not set, primitive types use `str::parse` because they do not implement
+/// `TryFrom<&str>`, others use `TryFrom<&str>`.
+fn convert_from_value(ty: &Type, convert: Option<Convert>) -> proc_macro2::TokenStream {
+    match convert.unwrap_or_else(|| {
+        if is_primitive(ty) {
+            Convert::Parse
+        } else {
+            Convert::TryFrom
+        }
+    }) {
+        Convert::TryFrom => quote! { <#ty>::try_from(value) },
+        Convert::Parse => quote! { value.parse::<#ty>() },
+    }
+}
+
+fn is_primitive(ty: &Type) -> bool {
+    const PRIMITIVES: [&str; 16] = [
+        "bool", "char", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64",
+        "u128", "usize", "f32", "f64",
+    ];
+    if let Type::Path(TypePath { qself: None, path }) = ty {
+        return path.get_ident().map_or(false, |ident| {
+            PRIMITIVES.contains(&ident.to_string().as_str())
+        });
+    }
+    false
+}
+
+/// Parse `#[autorule = "..."]` or `#[autorule(rule = "...", digits = "...", acronyms = [...])]`.
+fn parse_autorule(attr: &Attribute) -> Result<AutoRule, TokenStream> {
+    let mut rule_lit: Option<LitStr> = None;
+    let mut case_options = CaseOptions::default();
+    let mut acronyms_span = None;
+
+    match &attr.meta {
+        Meta::NameValue(MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(token, ..),
+                    ..
+                }),
+            ..
+        }) => rule_lit = Some(token.clone()),
+        Meta::List(_) => {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rule") {
+                    rule_lit = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("digits") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    case_options.digits = match value.value().as_str() {
+                        "attach_left" => DigitMode::AttachLeft,
+                        "separate" => DigitMode::Separate,
+                        _ => {
+                            return Err(syn::Error::new(
+                                value.span(),
+                                "unknown digits mode, available: attach_left, separate",
+                            ));
+                        }
+                    };
+                    Ok(())
+                } else if meta.path.is_ident("acronyms") {
+                    acronyms_span = Some(meta.path.span());
+                    let input = meta.value()?;
+                    let content;
+                    syn::bracketed!(content in input);
+                    for acronym in Punctuated::<LitStr, Token![,]>::parse_terminated(&content)? {
+                        let value = acronym.value();
+                        if value.is_empty() || !value.chars().all(char::is_alphanumeric) {
+                            return Err(syn::Error::new(
+                                acronym.span(),
+                                "acronym should be a non-empty word of letters and digits",
+                            ));
+                        }
+                        case_options.acronyms.push(value);
+                    }
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown autorule key, available keys: rule, digits, acronyms"))
+                }
+            })
+            .map_err(|e| TokenStream::from(e.to_compile_error()))?;
+        }
+        _ => {}
+    }
+
+    let token = match rule_lit {
+        Some(v) => v,
+        None => {
+            return Err(compiling_error!(
+                attr.meta.span(),
+                "expected #[autorule = \"...\"] or #[autorule(rule = \"...\")]"
+            ));
+        }
+    };
+
+    let rule = match token.value().as_str() {
+        "lowercase" => Rules::Lowercase,
+        "UPPERCASE" => Rules::Uppercase,
+        "camelCase" => Rules::CamelCase,
+        "PascalCase" => Rules::PascalCase,
+        "snake_case" => Rules::SnakeCase,
+        "SCREAMING_CASE" => Rules::ScreamingCase,
+        _ => {
+            return Err(compiling_error!(
+                token.span(),
+                "unknown AutoStr rules type: {}",
+                token.token()
+            ));
+        }
+    };
+
+    // Acronyms only change word boundaries, which lowercase and UPPERCASE do not have.
+    if let (Rules::Lowercase | Rules::Uppercase, Some(span)) = (&rule, acronyms_span) {
+        return Err(compiling_error!(
+            span,
+            "acronyms are not supported with {}, use them with camelCase, PascalCase, snake_case \
+or SCREAMING_CASE",
+            token.value()
+        ));
+    }
+
+    Ok(AutoRule { rule, case_options })
+}
+
+fn string_target_with_rule(rule: &Option<AutoRule>, str: &str) -> String {
     match rule {
-        Some(Rules::Lowercase) => str.to_lowercase(),
-        Some(Rules::Uppercase) => str.to_uppercase(),
-        Some(Rules::CamelCase) => to_camel_case(str),
-        Some(Rules::PascalCase) => to_pascal_case(str),
-        Some(Rules::SnakeCase) => to_snake_case(str),
-        Some(Rules::ScreamingCase) => to_screaming_case(str),
+        Some(AutoRule { rule, case_options }) => match rule {
+            Rules::Lowercase => str.to_lowercase(),
+            Rules::Uppercase => str.to_uppercase(),
+            Rules::CamelCase => to_camel_case_with(str, case_options),
+            Rules::PascalCase => to_pascal_case_with(str, case_options),
+            Rules::SnakeCase => to_snake_case_with(str, case_options),
+            Rules::ScreamingCase => to_screaming_case_with(str, case_options),
+        },
         None => str.to_string(),
     }
 }
@@ -0,0 +1,245 @@
+use proc_macro::TokenStream;
+
+use proc_macro2::{Delimiter, Group, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, Lit, LitStr, Token};
+
+use racros_core::{to_camel_case, to_pascal_case, to_screaming_case, to_snake_case};
+
+use crate::util::compiling_error;
+
+const ERROR_UNKNOWN_RULE: &str = "unknown case rule, available rules: snake, screaming, camel, \
+pascal, lower, upper (or snake_case, SCREAMING_CASE, camelCase, PascalCase, lowercase, UPPERCASE)";
+
+const NON_RAW_KEYWORDS: &[&str] = &["_", "crate", "self", "Self", "super"];
+
+/// Text to convert, the output is the same kind as the input by default.
+enum Target {
+    Ident(Ident),
+    Str(LitStr),
+}
+
+/// `case!(rule, target)` or `case!(rule, target, str|ident)`.
+struct CaseInput {
+    rule: Ident,
+    target: Target,
+    /// `Some(true)` for `str`, `Some(false)` for `ident`.
+    output_str: Option<bool>,
+}
+
+impl Parse for CaseInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let rule: Ident = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let target = if input.peek(LitStr) {
+            Target::Str(input.parse()?)
+        } else {
+            Target::Ident(input.parse()?)
+        };
+        let mut output_str = None;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            if !input.is_empty() {
+                let output: Ident = input.parse()?;
+                output_str = match output.to_string().as_str() {
+                    "str" => Some(true),
+                    "ident" => Some(false),
+                    _ => {
+                        return Err(syn::Error::new(
+                            output.span(),
+                            "unknown output kind, available kinds: str, ident",
+                        ));
+                    }
+                };
+                input.parse::<Option<Token![,]>>()?;
+            }
+        }
+        Ok(CaseInput {
+            rule,
+            target,
+            output_str,
+        })
+    }
+}
+
+pub fn case_internal(input: TokenStream) -> TokenStream {
+    // `case!(rule, target)` starts with a rule followed by a comma, anything else is a block where
+    // `[<rule target...>]` are substituted.
+    let mut tokens = TokenStream2::from(input.clone()).into_iter();
+    let is_call = matches!(
+        (tokens.next(), tokens.next()),
+        (Some(TokenTree::Ident(_)), Some(TokenTree::Punct(p))) if p.as_char() == ','
+    );
+    if !is_call {
+        return match substitute(input.into()) {
+            Ok(v) => v.into(),
+            Err(e) => e.to_compile_error().into(),
+        };
+    }
+
+    let CaseInput {
+        rule,
+        target,
+        output_str,
+    } = parse_macro_input!(input as CaseInput);
+
+    let (value, span, is_str) = match &target {
+        // Raw identifiers are converted without their `r#` prefix.
+        Target::Ident(v) => {
+            let value = v.to_string();
+            let value = value.strip_prefix("r#").unwrap_or(&value).to_string();
+            (value, v.span(), false)
+        }
+        Target::Str(v) => (v.value(), v.span(), true),
+    };
+    let value = value.as_str();
+
+    let converted = match convert(&rule, value) {
+        Some(v) => v,
+        None => return compiling_error!(rule.span(), "{}", ERROR_UNKNOWN_RULE),
+    };
+
+    if output_str.unwrap_or(is_str) {
+        let lit = LitStr::new(&converted, span);
+        return quote! { #lit }.into();
+    }
+
+    match to_ident(&converted, span) {
+        Some(ident) => quote! { #ident }.into(),
+        None => compiling_error!(
+            span,
+            "\"{}\" converted to \"{}\", which is not a valid identifier",
+            value,
+            converted
+        ),
+    }
+}
+
+fn convert(rule: &Ident, value: &str) -> Option<String> {
+    let converted = match rule.to_string().as_str() {
+        "snake" | "snake_case" => to_snake_case(value),
+        "screaming" | "SCREAMING_CASE" => to_screaming_case(value),
+        "camel" | "camelCase" => to_camel_case(value),
+        "pascal" | "PascalCase" => to_pascal_case(value),
+        "lower" | "lowercase" => value.to_lowercase(),
+        "upper" | "UPPERCASE" => value.to_uppercase(),
+        _ => return None,
+    };
+    Some(converted)
+}
+
+/// Replace every `[<rule target...>]` in `input` with the converted identifier.
+fn substitute(input: TokenStream2) -> syn::Result<TokenStream2> {
+    let mut output = TokenStream2::new();
+    for token in input {
+        match token {
+            TokenTree::Group(group) => {
+                let stream = group.stream();
+                let inner: Vec<TokenTree> = stream.clone().into_iter().collect();
+                if group.delimiter() == Delimiter::Bracket && is_angle_wrapped(&inner) {
+                    let ident = substitute_segments(&group, &inner[1..inner.len() - 1])?;
+                    output.extend(Some(TokenTree::Ident(ident)));
+                } else {
+                    let mut new_group = Group::new(group.delimiter(), substitute(stream)?);
+                    new_group.set_span(group.span());
+                    output.extend(Some(TokenTree::Group(new_group)));
+                }
+            }
+            _ => output.extend(Some(token)),
+        }
+    }
+    Ok(output)
+}
+
+fn is_angle_wrapped(tokens: &[TokenTree]) -> bool {
+    match (tokens.first(), tokens.last()) {
+        (Some(TokenTree::Punct(first)), Some(TokenTree::Punct(last))) => {
+            tokens.len() > 2 && first.as_char() == '<' && last.as_char() == '>'
+        }
+        _ => false,
+    }
+}
+
+/// Convert the content of `[<rule target...>]`, targets are concatenated before conversion.
+fn substitute_segments(group: &Group, tokens: &[TokenTree]) -> syn::Result<Ident> {
+    let mut segments = vec![];
+    flatten_segments(tokens, &mut segments);
+    let mut segments = segments.into_iter();
+    let rule = match segments.next() {
+        Some(TokenTree::Ident(v)) => v,
+        Some(v) => return Err(syn::Error::new(v.span(), "expected case rule")),
+        None => return Err(syn::Error::new(group.span(), "expected case rule")),
+    };
+
+    let mut value = String::new();
+    let mut span = None;
+    for segment in segments {
+        span.get_or_insert(segment.span());
+        match segment {
+            TokenTree::Ident(v) => {
+                let v = v.to_string();
+                value.push_str(v.strip_prefix("r#").unwrap_or(&v));
+            }
+            TokenTree::Literal(v) => match Lit::new(v) {
+                Lit::Str(v) => value.push_str(&v.value()),
+                v => value.push_str(&v.to_token_stream().to_string()),
+            },
+            v => {
+                return Err(syn::Error::new(
+                    v.span(),
+                    "expected identifier or literal in [<...>]",
+                ));
+            }
+        }
+    }
+    let span = match span {
+        Some(v) => v,
+        None => {
+            return Err(syn::Error::new(
+                group.span(),
+                "expected identifier to convert",
+            ))
+        }
+    };
+
+    let converted = match convert(&rule, &value) {
+        Some(v) => v,
+        None => return Err(syn::Error::new(rule.span(), ERROR_UNKNOWN_RULE)),
+    };
+    match to_ident(&converted, span) {
+        Some(v) => Ok(v),
+        None => Err(syn::Error::new(
+            span,
+            format!(
+                "\"{}\" converted to \"{}\", which is not a valid identifier",
+                value, converted
+            ),
+        )),
+    }
+}
+
+/// Unwrap invisible groups around `macro_rules!` fragments like `$name`.
+fn flatten_segments(tokens: &[TokenTree], output: &mut Vec<TokenTree>) {
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+                let inner: Vec<TokenTree> = group.stream().into_iter().collect();
+                flatten_segments(&inner, output);
+            }
+            _ => output.push(token.clone()),
+        }
+    }
+}
+
+/// Build an identifier from converted text, keywords are emitted as raw identifiers.
+fn to_ident(converted: &str, span: Span) -> Option<Ident> {
+    let mut ident = match syn::parse_str::<Ident>(converted) {
+        Ok(v) => v,
+        // These keywords can not be raw identifiers.
+        Err(_) if NON_RAW_KEYWORDS.contains(&converted) => return None,
+        Err(_) => syn::parse_str::<Ident>(&format!("r#{}", converted)).ok()?,
+    };
+    ident.set_span(span);
+    Some(ident)
+}
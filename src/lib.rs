@@ -19,6 +19,10 @@
 //! * Allow convert from multiple `String` values.
 //! * Set default convert style: `lowercase`, `UPPERCASE`, `camelCase`, `PascalCase` and
 //!   `snake_case`.
+//! * Newtype structs and unit structs are also supported.
+//!
+//! Case conversion is published in the `racros-core` crate, which gives the same results at
+//! runtime as `AutoStr` at compile time.
 //!
 //! ## [`CopyWith`]
 //!
@@ -28,6 +32,11 @@
 //! ## [`BundleText`]
 //!
 //! Bundle text content or command output into static str at compile time and use in runtime.
+//!
+//! ## [`case!`]
+//!
+//! Convert an identifier or a string literal to another case at compile time, e.g. generate a
+//! `http_client` function name from `HttpClient` inside `macro_rules!`.
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -36,15 +45,21 @@ use proc_macro::TokenStream;
 mod auto_debug;
 mod auto_str;
 mod bundle_text;
+mod case;
 mod copy_with;
 mod util;
 
-/// Automatically add [`TryFrom`] `String` and [`ToString`] trait to the attached enum.
+/// Automatically add [`TryFrom`] `String` and [`ToString`] trait to the attached enum or struct.
 ///
 /// # Usage:
 ///   * `str`: add `#[str("str1")]` to field will add
-/// the conversion from literal "str1" to that field
+///     the conversion from literal "str1" to that field
 ///   * Support using multiple str: `#str("str")]`.
+///   * Wrapped variants convert with `TryFrom<&str>` on the wrapped type, primitive types (integers,
+///     floats, `bool` and `char`) use `str::parse` instead. Set `#[str(convert = "parse")]` to use
+///     `str::parse` on any `FromStr` type, or `#[str(convert = "try_from")]` to force `TryFrom`.
+///   * Only non-empty lists of plain string literals are accepted in `#[str(...)]`, other
+///     literals, raw strings, unknown keys and `#[str()]` are compile errors.
 ///   * `#[autorule = "..." ]`, support autorules:
 ///     * `lowercase`.
 ///     * `UPPERCASE`.
@@ -52,6 +67,106 @@ mod util;
 ///     * `PascalCase`.
 ///     * `snake_case`.
 ///     * `SCREAMING_CASE`.
+///   * `#[autorule(rule = "...", digits = "...")]` also sets how digits are grouped into words:
+///     * `attach_left` (default): digits belong to the word before them, `Http2Client` =>
+///       `http2_client`.
+///     * `separate`: digits are a word of their own, `Http2Client` => `http_2_client`.
+///   * `#[autorule(rule = "...", acronyms = ["HTTP", "ID"])]` keeps listed acronyms as they are
+///     written in the list, in `camelCase`, `PascalCase`, `snake_case` and `SCREAMING_CASE`:
+///     `HttpClient` => `HTTPClient` in `PascalCase`, `UserId` => `userID` in `camelCase`.
+///     Words are matched case-insensitively and as a whole, `Identity` is not affected by `ID`.
+///     Acronyms with `lowercase` or `UPPERCASE` are compile errors.
+///   * `#[auto_str(round_trip)]` generates `check_round_trip()`, which converts every unit variant
+///     to string and back, returning the names of variants that do not convert back to themselves.
+///   * `#[auto_str(list)]` generates list helpers:
+///     * `parse_list(input, sep)` converts each trimmed item, errors carry the item index and
+///       position. One trailing separator is allowed, e.g. `"a,b,"`.
+///     * `parse_list_unique(input, sep)` also rejects values converting to the same string.
+///     * `join(items, sep)` joins `to_string` of items.
+///   * `#[auto_str(const_fn)]` generates `const fn from_str_const(s) -> Option<Self>` for enums
+///     made of unit variants, usable in `const` contexts.
+///   * `#[auto_str(json_schema)]` generates `json_schema() -> &'static str`, a JSON Schema listing
+///     accepted strings of each variant in `oneOf`, with the one used in `to_string` as
+///     `x-canonical` and doc comments as `description`. Wrapped variants need `#[str(...)]`.
+///   * `#[str(since = "2.1", until = "3.0")]` on variants generates
+///     `try_from_version(value, version)`, which rejects values not available in `version` (`since`
+///     is inclusive and `until` is exclusive) while `try_from` still accepts everything. The window
+///     applies to strings in the same `#[str(...)]`, or the whole variant if there is none.
+///   * `#[char('+')]` on any variant generates `TryFrom<char>` and `to_char()`. Variants without
+///     it use their single-character strings, it is a compile error if there is none.
+///
+/// ```compile_fail
+/// use racros::AutoStr;
+///
+/// #[derive(AutoStr)]
+/// #[autorule(rule = "lowercase", acronyms = ["HTTP"])] // error: acronyms are not supported
+/// enum Protocol {
+///     HttpClient,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use racros::AutoStr;
+///
+/// #[derive(AutoStr)]
+/// enum MyEnum {
+///     #[str(1)] // error: expected string literal in #[str(...)]
+///     E1,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use racros::AutoStr;
+///
+/// #[derive(AutoStr)]
+/// enum MyEnum {
+///     #[str()] // error: empty #[str()], expected at least one string
+///     E1,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use racros::AutoStr;
+///
+/// #[derive(AutoStr)]
+/// enum MyEnum {
+///     #[str(r"x")] // error: raw string literal is not supported in #[str(...)]
+///     E1,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use racros::AutoStr;
+///
+/// #[derive(AutoStr)]
+/// enum MyEnum {
+///     #[str(foo = "x")] // error: unknown AutoStr key on variant: foo
+///     E1,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use racros::AutoStr;
+///
+/// #[derive(AutoStr)]
+/// enum MyEnum {
+///     #[str(foo)] // error: expected string literal or `key = "value"` in #[str(...)]
+///     E1,
+/// }
+/// ```
+///
+/// ## Structs
+///
+///   * Unit structs convert from/to the strings in `#[str("str1", ...)]` on the struct, or the
+///     struct name with `#[autorule = "..."]`.
+///   * Newtype structs delegate to the wrapped type, with optional keys:
+///     * `#[str(prefix = "id-")]` requires and strips the prefix in `try_from`, prepends it in
+///       `to_string`.
+///     * `#[str(validate = "path::to::fn")]` checks the value (after prefix stripped) with a
+///       `fn(&str) -> bool` before converting.
+///     * `#[str(convert = "...")]` same as on wrapped variants.
+///   * Generic structs are supported, bounds needed by the conversion (e.g. `T: FromStr` and
+///     `T::Err: Display` with `convert = "parse"`) must be declared on the struct.
 ///
 /// # Example:
 ///
@@ -59,6 +174,7 @@ mod util;
 /// use racros::AutoStr;
 ///
 /// #[derive(AutoStr, Debug)]
+/// #[auto_str(const_fn)]
 /// enum MyEnum {
 ///     #[str("e1", "E1")]
 ///     E1,
@@ -86,6 +202,7 @@ mod util;
 /// }
 ///
 /// #[derive(AutoStr, Debug)]
+/// #[auto_str(round_trip, list)]
 /// enum MyEnum4 {
 ///     E41(MyEnum),
 ///     E42(MyEnum2),
@@ -97,6 +214,10 @@ mod util;
 /// assert!(matches!(MyEnum::try_from("ee"), Ok(MyEnum::E3)));
 /// assert!(matches!(MyEnum::try_from("e4"), Err(_)));
 ///
+/// const MY_ENUM_E3: Option<MyEnum> = MyEnum::from_str_const("ee");
+/// assert!(matches!(MY_ENUM_E3, Some(MyEnum::E3)));
+/// assert!(MyEnum::from_str_const("e4").is_none());
+///
 /// assert!(matches!(MyEnum2::try_from("E21"), Ok(MyEnum2::E21)));
 /// assert!(matches!(
 ///     MyEnum2::try_from("e1"),
@@ -135,8 +256,96 @@ mod util;
 ///     "failed to convert to MyEnum4 :invalid value \"e11\""
 /// );
 ///
+/// assert_eq!(MyEnum4::check_round_trip(), Ok(()));
+///
+/// let list = MyEnum4::parse_list("E1, E21", ",").unwrap();
+/// assert!(matches!(list[0], MyEnum4::E41(MyEnum::E1)));
+/// assert!(matches!(list[1], MyEnum4::E42(MyEnum2::E21)));
+/// assert_eq!(MyEnum4::join(&list, "|"), "e1|E21");
+/// assert!(MyEnum4::parse_list_unique("E1,e1", ",").is_err());
+///
+/// #[derive(AutoStr, Debug)]
+/// enum Addr {
+///     #[str("localhost")]
+///     Localhost,
+///     Port(u16),
+///     #[str(convert = "parse")]
+///     Ip(std::net::Ipv4Addr),
+/// }
+///
+/// assert!(matches!(Addr::try_from("8080"), Ok(Addr::Port(8080))));
+/// assert!(matches!(Addr::try_from("127.0.0.1"), Ok(Addr::Ip(_))));
+/// assert_eq!(Addr::Port(8080).to_string(), "8080");
+///
+/// #[derive(AutoStr, Debug)]
+/// #[autorule = "lowercase"]
+/// enum Format {
+///     #[str("json")]
+///     #[str("js", until = "2.0")]
+///     Json,
+///     #[str(since = "2.1")]
+///     Toml,
+/// }
+///
+/// assert!(matches!(Format::try_from_version("js", "1.5"), Ok(Format::Json)));
+/// assert!(Format::try_from_version("js", "2.0").is_err());
+/// assert!(Format::try_from_version("toml", "2.0").is_err());
+/// assert!(matches!(Format::try_from_version("toml", "2.1"), Ok(Format::Toml)));
+/// assert!(matches!(Format::try_from("toml"), Ok(Format::Toml)));
+///
+/// #[derive(AutoStr, Debug)]
+/// enum Op {
+///     #[char('+')]
+///     Add,
+///     #[str("-")]
+///     Sub,
+/// }
+///
+/// #[derive(AutoStr, Debug)]
+/// #[autorule(rule = "snake_case", digits = "separate")]
+/// enum Protocol {
+///     Http2Client,
+///     Ipv4Addr,
+/// }
+///
+/// assert_eq!(Protocol::Http2Client.to_string(), "http_2_client");
+/// assert_eq!(Protocol::Ipv4Addr.to_string(), "ipv_4_addr");
+///
+/// #[derive(AutoStr, Debug)]
+/// #[autorule(rule = "camelCase", acronyms = ["HTTP", "ID"])]
+/// enum Field {
+///     HttpClient,
+///     UserId,
+/// }
+///
+/// assert_eq!(Field::HttpClient.to_string(), "HTTPClient");
+/// assert_eq!(Field::UserId.to_string(), "userID");
+///
+/// assert!(matches!(Op::try_from('+'), Ok(Op::Add)));
+/// assert!(matches!(Op::try_from('-'), Ok(Op::Sub)));
+/// assert_eq!(Op::Add.to_char(), '+');
+///
+/// #[derive(AutoStr, Debug)]
+/// #[str(prefix = "id-", validate = "is_id")]
+/// struct MyId(String);
+///
+/// fn is_id(value: &str) -> bool {
+///     value.chars().all(|c| c.is_ascii_digit())
+/// }
+///
+/// #[derive(AutoStr, Debug)]
+/// #[str("marker", "m")]
+/// struct MyMarker;
+///
+/// assert_eq!(MyId::try_from("id-42").unwrap().0, "42");
+/// assert_eq!(MyId(String::from("42")).to_string(), "id-42");
+/// assert!(MyId::try_from("42").is_err());
+/// assert!(MyId::try_from("id-foo").is_err());
+/// assert!(matches!(MyMarker::try_from("m"), Ok(MyMarker)));
+/// assert_eq!(MyMarker.to_string(), "marker");
+///
 /// ```
-#[proc_macro_derive(AutoStr, attributes(str, autorule))]
+#[proc_macro_derive(AutoStr, attributes(str, char, autorule, auto_str))]
 pub fn auto_str(input: TokenStream) -> TokenStream {
     auto_str::auto_str_internal(input)
 }
@@ -247,27 +456,83 @@ pub fn copy_with(input: TokenStream) -> TokenStream {
 /// # Usage
 ///
 ///   * `#[derive(AutoDebug)]` makes a struct style debug implementation.
+///   * Tuple structs print in tuple style by default, field attributes work on their fields the
+///     same way, `debug_name` sets the name of a field if in struct `debug_style`.
+///   * Unit structs print their name.
 ///
 /// ## Struct Attributes
 ///
 ///   * `#[debug_style = tuple]` makes a tuple style debug implementation. Default is struct style.
-///   * `#[debug_format = display]` uses `Display` trait on fields. Default is debug format.
+///     `#[debug_style = map]` prints fields like a map, without the struct name.
+///   * `#[debug_truncate(max_len = 64, max_items = 10)]` truncates fields by default: `max_len`
+///     applies to `String` and `str` fields, `max_items` applies to `Vec`, `VecDeque`,
+///     `LinkedList`, `HashSet`, `BTreeSet`, slice and array fields. Also works on enums.
+///   * `#[debug_format = display]` uses `Display` trait on fields. Default is debug format, which
+///     follows the caller: fields are printed with `{:?}` in `{:?}` and with `{:#?}` in `{:#?}`.
 ///
 /// ## Struct Field Attributes
 ///
 ///   * `#[debug_name = "foo"]` override field name with "foo", if in struct `debug_style`.
 ///   * `#[debug_value = "foo"]` override field value with "foo".
+///   * `#[debug_value(expr)]` override field value with the value of `expr`, which can refer to
+///     `self` and to a reference to the field by the field name, or by `_0`, `_1`... for unnamed
+///     fields, e.g. `#[debug_value(items.len())]`.
 ///   * `#[debug_ignore]` will ignore this field in the output.
 ///   * `#[debug_debug]` will use `Debug` `{:#?}` for this field in output.
 ///   * `#[debug_display]` will use `Display` `{}` for this field in output.
 ///   * `#[debug_debug_not_pretty]` will use `Debug` `{:?}` for this field in output.
+///   * `#[debug_redact]` will print `***` instead of the value.
+///   * `#[debug_mask(keep_last = 4)]` will print `***` followed by the last 4 chars of the value,
+///     which should implement `AsRef<str>`. Values not longer than 4 chars print `***` only.
+///   * `#[debug_with = "path::to::fn"]` will print this field with the function, which has the
+///     signature `fn(&T, &mut std::fmt::Formatter) -> std::fmt::Result` where `T` is the field type.
+///   * `#[debug_truncate(max_len = 64)]` will print at most 64 chars of this field, which should
+///     implement `AsRef<str>`, followed by `... (N more)` if truncated.
+///   * `#[debug_truncate(max_items = 10)]` will print at most 10 items of this field as a list,
+///     followed by a `... (N more)` item if truncated. The field should have an `iter()` method.
+///   * `#[debug_skip_if = "path::to::fn"]` will leave this field out of the output when the
+///     predicate returns true, which has the signature `fn(&T) -> bool` where `T` is the field
+///     type, e.g. `Option::is_none` or `Vec::is_empty`.
 ///
 /// ## Enum Variant Attributes
-///   * `#[debug_ignore]`
+///   * `#[debug_name = "foo"]` override variant name with "foo".
+///   * `#[debug_ignore]` will print the variant name only, ignoring all fields in it.
 ///   * `#[debug_debug]`
 ///   * `#[debug_display]`
 ///   * `#[debug_debug_not_pretty]`
 ///
+/// Format attributes on a variant set the default format of fields in it. Other attributes are
+/// compile errors on variants.
+///
+/// Unit variants print their name without quotes. Struct variants print in struct style and tuple
+/// variants print in tuple style, like `#[derive(Debug)]`. `#[debug_style = "..."]` on the enum
+/// sets the style of all variants: `struct`, `tuple` or `map`.
+///
+/// ## Enum Variant Field Attributes
+///
+/// Fields inside enum variants support the same attributes as struct fields. `#[debug_redact]` and
+/// `#[debug_mask(...)]` must be placed on the fields to hide, they are compile errors on variants:
+///
+/// ```compile_fail
+/// use racros::AutoDebug;
+///
+/// #[derive(AutoDebug)]
+/// enum Credential {
+///     #[debug_redact] // error: debug_redact/debug_mask must be placed on fields
+///     Token(String),
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use racros::AutoDebug;
+///
+/// #[derive(AutoDebug)]
+/// enum Response {
+///     #[debug_skip_if = "Option::is_none"] // error: debug_skip_if is not supported on enum variants
+///     Reason(Option<String>),
+/// }
+/// ```
+///
 /// # Example
 ///
 /// ```
@@ -340,6 +605,34 @@ pub fn copy_with(input: TokenStream) -> TokenStream {
 /// )"#
 ///     );
 ///
+/// #[derive(AutoDebug)]
+/// struct Account {
+///     name: String,
+///     #[debug_redact]
+///     password: String,
+///     #[debug_mask(keep_last = 4)]
+///     card: String,
+/// }
+///
+/// let account = Account {
+///     name: String::from("foo"),
+///     password: String::from("secret"),
+///     card: String::from("1234567812345678"),
+/// };
+/// assert_eq!(
+///     format(format_args!("{:?}", account)),
+///     r#"Account { name: "foo", password: ***, card: ***5678 }"#
+/// );
+///
+/// #[derive(AutoDebug)]
+/// struct Meters(f64);
+///
+/// #[derive(AutoDebug)]
+/// struct Marker;
+///
+/// assert_eq!(format(format_args!("{:?}", Meters(1.5))), "Meters(1.5)");
+/// assert_eq!(format(format_args!("{:?}", Marker)), "Marker");
+///
 /// // Enum
 ///
 /// #[derive(AutoDebug)]
@@ -369,7 +662,7 @@ pub fn copy_with(input: TokenStream) -> TokenStream {
 /// let foo34 = Foo3::Foo4 { a: -100, b: 200 };
 /// assert_eq!(
 ///     format(format_args!("{:#?}", foo34)),
-///     r#"{
+///     r#"Foo4 {
 ///     a: -100,
 ///     b: 200,
 /// }"#
@@ -396,6 +689,11 @@ pub fn copy_with(input: TokenStream) -> TokenStream {
         debug_debug,
         debug_display,
         debug_debug_not_pretty,
+        debug_redact,
+        debug_mask,
+        debug_with,
+        debug_truncate,
+        debug_skip_if,
     )
 )]
 pub fn auto_debug(input: TokenStream) -> TokenStream {
@@ -443,3 +741,74 @@ pub fn auto_debug(input: TokenStream) -> TokenStream {
 pub fn bundle_text(input: TokenStream) -> TokenStream {
     bundle_text::bundle_text_internal(input)
 }
+
+/// Convert an identifier or a string literal to another case at compile time.
+///
+/// # Usage
+///
+/// `case!(rule, input)` or `case!(rule, input, output)`:
+///
+/// * `rule` is one of:
+///   * `snake` or `snake_case`.
+///   * `screaming` or `SCREAMING_CASE`.
+///   * `camel` or `camelCase`.
+///   * `pascal` or `PascalCase`.
+///   * `lower` or `lowercase`.
+///   * `upper` or `UPPERCASE`.
+/// * `input` is an identifier or a string literal, converted the same way as `#[autorule]` in
+///   [`AutoStr`].
+/// * `output` is `ident` or `str`, defaults to the kind of `input`. Keywords are output as raw
+///   identifiers, e.g. `r#type`, and it is a compile error if the converted string is not a valid
+///   identifier when outputting an identifier. The `r#` prefix of a raw identifier `input` is not
+///   converted, while string literals are converted as they are.
+///
+/// The output has the span of `input`, so identifiers generated from `macro_rules!` arguments
+/// resolve at the caller. Like any macro call, `case!(...)` can be used in expressions, patterns
+/// and types, but not as the name of an item.
+///
+/// To name items, wrap them in `case! { ... }`: every `[<rule input...>]` inside is replaced with
+/// the converted identifier. Inputs are identifiers or literals, concatenated before conversion,
+/// and the identifier has the span of the first input.
+///
+/// # Example
+///
+/// ```
+/// use racros::case;
+///
+/// fn http_client() -> &'static str {
+///     "client"
+/// }
+///
+/// macro_rules! call {
+///     ($name:ident) => {
+///         (case!(snake, $name)(), case!(screaming, $name, str))
+///     };
+/// }
+///
+/// assert_eq!(call!(HttpClient), ("client", "HTTP_CLIENT"));
+/// assert_eq!(case!(snake, "HttpClient"), "http_client");
+/// assert_eq!(case!(camel, "HTTP_CLIENT"), "httpClient");
+///
+/// let case!(snake, MyValue) = 1;
+/// assert_eq!(my_value, 1);
+///
+/// macro_rules! define {
+///     ($name:ident, $value:expr) => {
+///         case! {
+///             fn [<snake $name>]() -> u32 {
+///                 [<screaming $name>]
+///             }
+///
+///             const [<screaming $name>]: u32 = $value;
+///         }
+///     };
+/// }
+///
+/// define!(RetryCount, 3);
+/// assert_eq!(retry_count(), 3);
+/// assert_eq!(RETRY_COUNT, 3);
+/// ```
+#[proc_macro]
+pub fn case(input: TokenStream) -> TokenStream {
+    case::case_internal(input)
+}